@@ -25,6 +25,17 @@ pub enum DisjointMove<L: Game, R: Game> {
     RightMove(R::Move),
 }
 
+// deriving `PartialEq` would require `L` and `R` to be `PartialEq` instead of their moves
+impl<L: Game, R: Game> PartialEq for DisjointMove<L, R> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::LeftMove(a), Self::LeftMove(b)) => a == b,
+            (Self::RightMove(a), Self::RightMove(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
 #[derive(Debug, Error, Clone)]
 pub enum DisjointMoveError<L: Game, R: Game> {
     #[error("Could not make the move on left: {0}")]
@@ -33,6 +44,15 @@ pub enum DisjointMoveError<L: Game, R: Game> {
     RightError(R::MoveError),
 }
 
+// the sum is the same position as long as both of its games are
+impl<L: Game + NormalImpartial, R: Game + NormalImpartial> From<DisjointImpartialNormalGame<L, R>>
+    for (L::Key, R::Key)
+{
+    fn from(game: DisjointImpartialNormalGame<L, R>) -> Self {
+        (game.left.key(), game.right.key())
+    }
+}
+
 type LeftMoveMap<L, R> = Box<dyn Fn(<L as Game>::Move) -> DisjointMove<L, R>>;
 type RightMoveMap<L, R> = Box<dyn Fn(<R as Game>::Move) -> DisjointMove<L, R>>;
 
@@ -57,6 +77,7 @@ impl<L: Game + Debug + NormalImpartial + 'static, R: Game + Debug + NormalImpart
 
     type Player = ImpartialPlayer;
     type MoveError = DisjointMoveError<L, R>;
+    type Key = (L::Key, R::Key);
 
     fn move_count(&self) -> usize {
         self.left.move_count() + self.right.move_count()
@@ -65,8 +86,7 @@ impl<L: Game + Debug + NormalImpartial + 'static, R: Game + Debug + NormalImpart
     fn max_moves(&self) -> Option<usize> {
         self.left
             .max_moves()
-            .map(|l| self.right.max_moves().map(|r| l + r))
-            .flatten()
+            .and_then(|l| self.right.max_moves().map(|r| l + r))
     }
 
     fn make_move(&mut self, m: &Self::Move) -> Result<(), Self::MoveError> {
@@ -102,7 +122,119 @@ impl<L: Game + Debug + NormalImpartial + 'static, R: Game + Debug + NormalImpart
     }
 
     fn state(&self) -> crate::game::GameState<Self::Player> {
-        <Self as Normal>::state(&self)
+        <Self as Normal>::state(self)
+    }
+
+    fn player(&self) -> Self::Player {
+        ImpartialPlayer::Next
+    }
+}
+
+/// Represents the disjoint sum of any amount of
+/// impartial normal combinatorial games of the same type.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct DisjointImpartialNormalGameVec<G: Game + NormalImpartial> {
+    games: Vec<G>,
+}
+
+impl<G: Game + NormalImpartial> DisjointImpartialNormalGameVec<G> {
+    pub fn new(games: Vec<G>) -> Self {
+        Self { games }
+    }
+
+    pub fn games(&self) -> &[G] {
+        &self.games
+    }
+}
+
+impl<G: Game + NormalImpartial> From<DisjointImpartialNormalGameVec<G>> for Vec<G::Key> {
+    fn from(game: DisjointImpartialNormalGameVec<G>) -> Self {
+        game.games.iter().map(Game::key).collect()
+    }
+}
+
+/// A move made on the game at `index` of a [`DisjointImpartialNormalGameVec`].
+#[derive(Clone)]
+pub struct DisjointVecMove<G: Game> {
+    pub index: usize,
+    pub m: G::Move,
+}
+
+impl<G: Game> PartialEq for DisjointVecMove<G> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index && self.m == other.m
+    }
+}
+
+#[derive(Debug, Error, Clone)]
+pub enum DisjointVecMoveError<G: Game> {
+    #[error("game {index} is out of bounds of the amount of games {game_count}")]
+    OutOfBounds { index: usize, game_count: usize },
+    #[error("Could not make the move on game {0}: {1}")]
+    GameError(usize, G::MoveError),
+}
+
+impl<G: Game + Debug + NormalImpartial> Normal for DisjointImpartialNormalGameVec<G> {}
+
+impl<G: Game + Debug + NormalImpartial> NormalImpartial for DisjointImpartialNormalGameVec<G> {
+    fn split(&self) -> Option<Vec<Self>> {
+        if self.games.len() < 2 {
+            return None;
+        }
+
+        Some(
+            self.games
+                .iter()
+                .map(|game| Self::new(vec![game.clone()]))
+                .collect(),
+        )
+    }
+}
+
+impl<G: Game + Debug + NormalImpartial> Game for DisjointImpartialNormalGameVec<G> {
+    type Move = DisjointVecMove<G>;
+    type Iter<'a> = std::vec::IntoIter<Self::Move> where G: 'a;
+
+    type Player = ImpartialPlayer;
+    type MoveError = DisjointVecMoveError<G>;
+    type Key = Vec<G::Key>;
+
+    fn move_count(&self) -> usize {
+        self.games.iter().map(Game::move_count).sum()
+    }
+
+    fn max_moves(&self) -> Option<usize> {
+        self.games.iter().map(Game::max_moves).sum()
+    }
+
+    fn make_move(&mut self, m: &Self::Move) -> Result<(), Self::MoveError> {
+        let game_count = self.games.len();
+        let game = self
+            .games
+            .get_mut(m.index)
+            .ok_or(DisjointVecMoveError::OutOfBounds {
+                index: m.index,
+                game_count,
+            })?;
+
+        game.make_move(&m.m)
+            .map_err(|err| DisjointVecMoveError::GameError(m.index, err))
+    }
+
+    fn possible_moves(&self) -> Self::Iter<'_> {
+        self.games
+            .iter()
+            .enumerate()
+            .flat_map(|(index, game)| {
+                game.possible_moves()
+                    .map(move |m| DisjointVecMove { index, m })
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn state(&self) -> crate::game::GameState<Self::Player> {
+        <Self as Normal>::state(self)
     }
 
     fn player(&self) -> Self::Player {
@@ -1,19 +1,100 @@
 //! Game trait and related types.
 
-use std::{cmp::Ordering, error::Error};
+use std::{cmp::Ordering, error::Error, fmt::Display, hash::Hash};
 
 use crate::player::Player;
 
 /// Represents a move outcome
-#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash)]
+#[derive(PartialEq, Eq, Debug, Clone, Hash)]
 pub enum GameState<P: Player> {
     /// It is still a player's turn - the game continues.
     Playable,
     /// The game ended in a tie - no players won
     Tie,
-    // TODO: handling non-unique player wins.
     /// A player won.
     Win(P),
+    /// Some players shared the win, and every other player lost.
+    ///
+    /// Every winner should only be listed once.
+    WinMany(Vec<P>),
+}
+
+impl<P: Player> GameState<P> {
+    /// Collapses a [`GameState::WinMany`] into a simpler state, if there is one:
+    /// a win with a single winner is a [`GameState::Win`],
+    /// and a win shared by no players or every player is a [`GameState::Tie`].
+    ///
+    /// In two-player games, this leaves no `WinMany` behind.
+    pub fn normalize(self) -> Self {
+        match self {
+            Self::WinMany(mut winners) => {
+                if winners.len() == 1 {
+                    Self::Win(winners.remove(0))
+                } else if winners.is_empty() || winners.len() >= P::count() {
+                    Self::Tie
+                } else {
+                    Self::WinMany(winners)
+                }
+            }
+            state => state,
+        }
+    }
+
+    /// Whether the game can still be played.
+    ///
+    /// ```
+    /// use game_solver::{game::GameState, player::PartizanPlayer};
+    ///
+    /// assert!(GameState::<PartizanPlayer>::Playable.is_playable());
+    /// assert!(!GameState::<PartizanPlayer>::Tie.is_playable());
+    /// ```
+    pub fn is_playable(&self) -> bool {
+        matches!(self, Self::Playable)
+    }
+
+    /// Whether the game is over, whether it was won or tied.
+    ///
+    /// ```
+    /// use game_solver::{game::GameState, player::PartizanPlayer};
+    ///
+    /// assert!(GameState::Win(PartizanPlayer::Left).is_terminal());
+    /// assert!(GameState::WinMany(vec![PartizanPlayer::Left]).is_terminal());
+    /// assert!(!GameState::<PartizanPlayer>::Playable.is_terminal());
+    /// ```
+    pub fn is_terminal(&self) -> bool {
+        !self.is_playable()
+    }
+
+    /// The only winner of the game, if it was won by a single player.
+    ///
+    /// A win shared by several players has no single winner - use [`GameState::is_winner`]
+    /// to check those, or [`GameState::normalize`] it first.
+    ///
+    /// ```
+    /// use game_solver::{game::GameState, player::PartizanPlayer};
+    ///
+    /// assert_eq!(GameState::Win(PartizanPlayer::Right).winner(), Some(&PartizanPlayer::Right));
+    /// assert_eq!(GameState::<PartizanPlayer>::Tie.winner(), None);
+    /// assert_eq!(
+    ///     GameState::WinMany(vec![PartizanPlayer::Left]).normalize().winner(),
+    ///     Some(&PartizanPlayer::Left)
+    /// );
+    /// ```
+    pub fn winner(&self) -> Option<&P> {
+        match self {
+            Self::Win(winner) => Some(winner),
+            Self::Playable | Self::Tie | Self::WinMany(_) => None,
+        }
+    }
+
+    /// Whether `player` won the game, alone or with other players.
+    pub fn is_winner(&self, player: &P) -> bool {
+        match self {
+            Self::Win(winner) => winner == player,
+            Self::WinMany(winners) => winners.contains(player),
+            Self::Playable | Self::Tie => false,
+        }
+    }
 }
 
 /// Marks a game as being 'normal' (a game has the 'normal play' convention).
@@ -25,11 +106,7 @@ pub enum GameState<P: Player> {
 /// Learn more: <https://en.wikipedia.org/wiki/Normal_play_convention>
 pub trait Normal: Game {
     fn state(&self) -> GameState<Self::Player> {
-        if self.possible_moves().next().is_none() {
-            GameState::Win(self.player().previous())
-        } else {
-            GameState::Playable
-        }
+        StateType::Normal.state(self)
     }
 }
 
@@ -43,6 +120,8 @@ pub trait NormalImpartial: Normal {
     /// using the technique described in [Nimbers Are Inevitable](https://arxiv.org/abs/1011.5841).
     ///
     /// Returns `Option::None`` if the game currently can not be split.
+    /// Every returned game must be smaller than this one, so a game
+    /// that can't be split into at least two games should return `None`.
     fn split(&self) -> Option<Vec<Self>> {
         None
     }
@@ -50,17 +129,46 @@ pub trait NormalImpartial: Normal {
 
 /// Marks a game as being 'misere' (a game has the 'misere play' convention).
 ///
-/// Rather, this means that the game is lost by whoever plays last.
+/// Rather, this means that the game is lost by whoever plays last -
+/// in games with more than two players, every other player shares the win.
 /// Under this convention, no ties are possible: there has to exist a strategy
 /// for players to be able to force a win.
 ///
 /// Learn more: <https://en.wikipedia.org/wiki/Mis%C3%A8re#Mis%C3%A8re_game>
 pub trait Misere: Game {
-    fn state<T>(&self) -> GameState<Self::Player> {
-        if self.possible_moves().next().is_none() {
-            GameState::Win(self.player())
-        } else {
-            GameState::Playable
+    fn state(&self) -> GameState<Self::Player> {
+        StateType::Misere.state(self)
+    }
+}
+
+/// The win condition of a game that ends when there are no moves left.
+///
+/// This is useful for games that can be played under either convention,
+/// such as Nim, where [`Normal`] and [`Misere`] can't both be implemented.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash)]
+pub enum StateType {
+    /// The last player to move wins.
+    Normal,
+    /// The last player to move loses.
+    Misere,
+}
+
+impl StateType {
+    /// Returns the current state of `game` under this convention.
+    pub fn state<T: Game>(&self, game: &T) -> GameState<T::Player> {
+        if game.possible_moves().next().is_some() {
+            return GameState::Playable;
+        }
+
+        match self {
+            Self::Normal => GameState::Win(game.player().previous()),
+            // everyone but the player who moved last, starting from the player to move
+            Self::Misere => GameState::WinMany(
+                (0..T::Player::count() - 1)
+                    .map(|offset| (0..offset).fold(game.player(), |player, _| player.next()))
+                    .collect(),
+            )
+            .normalize(),
         }
     }
 }
@@ -74,7 +182,10 @@ pub trait Misere: Game {
 /// - Game player count: >0
 pub trait Game: Clone {
     /// The type of move this game uses.
-    type Move: Clone;
+    ///
+    /// Moves are compared to find [killer moves](https://www.chessprogramming.org/Killer_Heuristic)
+    /// among the possible moves of a game.
+    type Move: Clone + PartialEq;
 
     /// The iterator type for possible moves.
     type Iter<'a>: Iterator<Item = Self::Move> + 'a
@@ -85,15 +196,55 @@ pub trait Game: Clone {
 
     type Player: Player;
 
+    /// What makes two games the same position for the solver,
+    /// which stores games in transposition tables by their [`Game::key`].
+    ///
+    /// This is usually `Self`, but games whose equality is too strict or too expensive
+    /// to find repeated positions with can use a separate key type instead.
+    type Key: Eq + Hash + From<Self>;
+
     /// Returns the amount of moves that have been played
     fn move_count(&self) -> usize;
 
     /// Get the max number of moves in a game, if any.
+    ///
+    /// Games without one (such as loopy games) can only be solved exactly if they always end,
+    /// e.g. by tracking repeated positions with [`LoopyTracker`](crate::loopy::LoopyTracker).
+    /// Games that can go on forever need to be solved with
+    /// [`solve_depth_limited`](crate::solve_depth_limited) and a [`Game::heuristic`] instead -
+    /// an exact search fails with [`GameSolveError::Unbounded`](crate::GameSolveError::Unbounded)
+    /// once it goes [`MAX_UNBOUNDED_PLY`](crate::MAX_UNBOUNDED_PLY) moves deep.
     fn max_moves(&self) -> Option<usize>;
 
     /// Makes a move.
     fn make_move(&mut self, m: &Self::Move) -> Result<(), Self::MoveError>;
 
+    /// Whether this game implements [`Game::undo_move`].
+    ///
+    /// If it does, the solver makes and undoes every move on the same board,
+    /// instead of cloning the board for every move.
+    const SUPPORTS_UNDO: bool = false;
+
+    /// Undoes `m`, which has to be the last move made on this game.
+    ///
+    /// This is only called if [`Game::SUPPORTS_UNDO`] is true.
+    fn undo_move(&mut self, _m: &Self::Move) -> Result<(), Self::MoveError> {
+        unimplemented!("this game can not undo moves")
+    }
+
+    /// Whether the player whose turn it is still has the turn after making `m`,
+    /// such as a capture that forces the capturing player to keep capturing.
+    ///
+    /// If this is true, [`Game::player`] must return the same player after `m` is made,
+    /// and the solver scores the game after `m` from the same player's perspective
+    /// instead of its opponent's. Returns `false` by default.
+    ///
+    /// Solvers for impartial games, such as [`grundy_value`](crate::grundy_value),
+    /// assume turns always alternate, so they ignore this.
+    fn same_player_after(&self, _m: &Self::Move) -> bool {
+        false
+    }
+
     /// Returns an iterator of all possible moves.
     ///
     /// If possible, this function should "guess" what the best moves are first.
@@ -101,42 +252,55 @@ pub trait Game: Clone {
     /// Since "better" moves would be found first, this permits more alpha/beta cutoffs.
     fn possible_moves(&self) -> Self::Iter<'_>;
 
-    /// Returns a reachable game in one move.
+    /// Returns an iterator of every game reachable by making one of [`Game::possible_moves`],
+    /// in the same order.
+    fn successors(&self) -> impl Iterator<Item = Result<Self, Self::MoveError>> + '_ {
+        self.possible_moves().map(move |m| {
+            let mut board = self.clone();
+            board.make_move(&m).map(|()| board)
+        })
+    }
+
+    /// Returns how promising `m` is, so the solver can try better moves first.
     ///
-    /// Rather, this function asks if there exists some game in the possible games set
-    /// which has a resolvable, positive or negative, outcome.
+    /// Moves with a higher key are searched first, and moves with equal keys
+    /// keep the order given by [`Game::possible_moves`].
+    /// This is useful when the natural order of moves is a poor guess,
+    /// such as cells in scan order. Returns `0` by default.
+    fn move_order_key(&self, _m: &Self::Move) -> i32 {
+        0
+    }
+
+    /// Describes what playing `m` in this game does, for people following along -
+    /// such as "X plays the center" instead of just the square's coordinates.
+    ///
+    /// Returns the [`Display`] of `m` by default.
+    fn describe_move(&self, m: &Self::Move) -> String
+    where
+        Self::Move: Display,
+    {
+        m.to_string()
+    }
+
+    /// Returns a game one move away from this one that has already ended, if there is one.
+    ///
+    /// This function must act in the Next player's best interest:
+    /// - If any move wins the game for the player to move, one of those games must be returned.
+    /// - Otherwise, a tied game should be returned over a lost game.
     ///
-    /// This function must act in the Next player's best interest.
-    /// Positive games should have highest priority, then tied games, then lost games.
-    /// Exact order of what game is returned doesn't matter past its outcome equivalency,
-    /// as the score is dependent on move count.
+    /// Which game is returned doesn't matter past its outcome,
+    /// as the score only depends on the move count, which is the same for every move.
+    /// Returning `None` (or a tie or loss) when a move wins immediately violates this contract,
+    /// and makes the solver explore the rest of the game for nothing.
     ///
-    /// (If this function returns a losing game when a positive game exists
-    /// in the set of immediately resolvable games, that is a violation of this
-    /// function's contract).
+    /// Since the solver only stops early for immediate wins, implementations may return `None`
+    /// when no move wins. This is how it should be overridden: by ruling out immediate wins
+    /// with a cheap check, and falling back to [`naive_immediately_resolvable_game`] otherwise.
     ///
-    /// This function's default implementation is quite slow,
-    /// and it's encouraged to use a custom implementation.
+    /// The default implementation is [`naive_immediately_resolvable_game`],
+    /// which makes and checks every move, so it's quite slow.
     fn find_immediately_resolvable_game(&self) -> Result<Option<Self>, Self::MoveError> {
-        let mut best_non_winning_game: Option<Self> = None;
-
-        for m in &mut self.possible_moves() {
-            let mut new_self = self.clone();
-            new_self.make_move(&m)?;
-            match new_self.state() {
-                GameState::Playable => continue,
-                GameState::Tie => best_non_winning_game = Some(new_self),
-                GameState::Win(winning_player) => {
-                    if winning_player == self.player().turn() {
-                        return Ok(Some(new_self));
-                    } else if best_non_winning_game.is_none() {
-                        best_non_winning_game = Some(new_self)
-                    }
-                }
-            };
-        }
-
-        Ok(best_non_winning_game)
+        naive_immediately_resolvable_game(self)
     }
 
     /// Returns the current state of the game.
@@ -182,6 +346,99 @@ pub trait Game: Clone {
     /// However, no implementation is provided
     /// because this does not keep track of the move count.
     fn player(&self) -> Self::Player;
+
+    /// Whether this game implements [`Game::heuristic`].
+    ///
+    /// Depth-limited solving falls back to exact solving if this is false.
+    const HAS_HEURISTIC: bool = false;
+
+    /// Estimates the score of a position that isn't searched any deeper,
+    /// from the perspective of the player whose turn it is.
+    ///
+    /// Positive values favor the player whose turn it is, and negative values favor the other player.
+    /// Since heuristic scores share the same domain as exact scores, this should stay well within
+    /// [`upper_bound`] - a heuristic of `upper_bound(game)` is as good as an immediate win.
+    ///
+    /// This is only called if [`Game::HAS_HEURISTIC`] is true.
+    fn heuristic(&self) -> isize {
+        unimplemented!("this game does not have a heuristic")
+    }
+
+    /// Whether this game implements [`Game::terminal_value`].
+    ///
+    /// Scores of these games are margins rather than distances to the end of the game,
+    /// so [`score_to_outcome`] doesn't report how many moves away a win or loss is.
+    const HAS_TERMINAL_VALUE: bool = false;
+
+    /// Returns the score of a won game from the perspective of the player whose turn it is,
+    /// if the result of this game is measured by more than who won - such as a difference in points.
+    ///
+    /// When this returns `Some`, the solver uses it instead of scoring wins by how fast they are,
+    /// so larger wins are preferred over smaller ones. The value should be positive if the player
+    /// whose turn it is won, negative if they lost, and stay within `1..=upper_bound(game)` in magnitude.
+    ///
+    /// This is only called if [`Game::HAS_TERMINAL_VALUE`] is true,
+    /// on games whose state is [`GameState::Win`]. Returns `None` by default.
+    fn terminal_value(&self) -> Option<isize> {
+        None
+    }
+
+    /// Whether this game is still playable, but can only end in a tie no matter how it's played,
+    /// such as a Tic Tac Toe board where every line already holds both symbols.
+    ///
+    /// The solver scores these games as `0` without searching them any further,
+    /// so this should be cheap to check. Returns `false` by default.
+    fn is_forced_draw(&self) -> bool {
+        false
+    }
+
+    /// Returns a canonical form of this game, if the game has symmetries.
+    ///
+    /// Every game that is equivalent to this one under the game's symmetries
+    /// (e.g. rotating or reflecting the board) should return the same canonical form,
+    /// which must have the same score as this game.
+    /// The solver uses its [`Game::key`] as the transposition table key,
+    /// so equivalent games are only solved once.
+    ///
+    /// Returns `None` if this game shouldn't be canonicalized, which is the default.
+    fn canonical_key(&self) -> Option<Self> {
+        None
+    }
+
+    /// Returns the key of this game in transposition tables.
+    ///
+    /// By default, this converts the [`Game::canonical_key`] of this game
+    /// (or this game itself, if it doesn't have one) into a [`Game::Key`].
+    fn key(&self) -> Self::Key {
+        self.canonical_key().unwrap_or_else(|| self.clone()).into()
+    }
+}
+
+/// The default [`Game::find_immediately_resolvable_game`],
+/// which makes every move of `game` and returns the best one that ends it.
+///
+/// This can be used to check faster implementations against,
+/// or as their fallback when a cheap check can't rule out an immediate win.
+pub fn naive_immediately_resolvable_game<T: Game>(game: &T) -> Result<Option<T>, T::MoveError> {
+    let mut best_non_winning_game: Option<T> = None;
+
+    for m in &mut game.possible_moves() {
+        let mut new_game = game.clone();
+        new_game.make_move(&m)?;
+        match new_game.state().normalize() {
+            GameState::Playable => continue,
+            GameState::Tie | GameState::WinMany(_) => best_non_winning_game = Some(new_game),
+            GameState::Win(winning_player) => {
+                if winning_player == game.player().turn() {
+                    return Ok(Some(new_game));
+                } else if best_non_winning_game.is_none() {
+                    best_non_winning_game = Some(new_game)
+                }
+            }
+        };
+    }
+
+    Ok(best_non_winning_game)
 }
 
 /// Utility function to get the upper score bound of a game.
@@ -190,35 +447,84 @@ pub trait Game: Clone {
 /// and penalizes the score by the amount of moves that have been made, as we're
 /// trying to encourage winning in the shortest amount of time - God's algorithm.
 ///
+/// Games without [`Game::max_moves`] use [`UNBOUNDED_UPPER_BOUND`].
+///
+/// Games with [`Game::HAS_TERMINAL_VALUE`] use this to bound their margins instead,
+/// so their scores can't be turned back into a distance.
+///
 /// Note: Despite this returning isize, this function will always be positive.
 pub fn upper_bound<T: Game>(game: &T) -> isize {
-    game.max_moves().map_or(
-        // TODO(HACKY): theres probably nicer ways of handling upper bounds for
-        // loopy games
-        isize::MAX / 2, |m| m as isize
-    )
+    game.max_moves()
+        .map_or(UNBOUNDED_UPPER_BOUND, |m| m as isize)
 }
 
+/// The upper score bound of games without [`Game::max_moves`].
+///
+/// This is half of `isize::MAX`, so scores within it can be negated,
+/// widened by one for null windows, and summed without overflowing.
+pub const UNBOUNDED_UPPER_BOUND: isize = isize::MAX / 2;
+
 /// Represents an outcome of a game derived by a score and a valid instance of a game.
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum GameScoreOutcome {
-    /// The inner field represents the amount of moves till a win.
-    Win(usize),
-    /// The inner field represents the amount of moves till a loss.
-    Loss(usize),
+    /// The amount of moves till a win, if it's known.
+    Win {
+        distance: Option<usize>,
+    },
+    /// The amount of moves till a loss, if it's known.
+    Loss {
+        distance: Option<usize>,
+    },
     Tie,
 }
 
+impl PartialOrd for GameScoreOutcome {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Outcomes are ordered by how desirable they are for the player to move:
+/// faster wins are better than slower wins, wins are better than ties,
+/// and slower losses are better than faster losses.
+///
+/// Unknown distances are treated as being further away than any known distance.
+impl Ord for GameScoreOutcome {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let far = |distance: &Option<usize>| distance.unwrap_or(usize::MAX);
+
+        match (self, other) {
+            (Self::Win { distance: a }, Self::Win { distance: b }) => far(b).cmp(&far(a)),
+            (Self::Loss { distance: a }, Self::Loss { distance: b }) => far(a).cmp(&far(b)),
+            (Self::Tie, Self::Tie) => Ordering::Equal,
+            (Self::Win { .. }, _) | (Self::Tie, Self::Loss { .. }) => Ordering::Greater,
+            (Self::Loss { .. }, _) | (Self::Tie, Self::Win { .. }) => Ordering::Less,
+        }
+    }
+}
+
 /// Utility function to convert a score to the
 /// amount of moves to a win or loss, or a tie.
+///
+/// Games scored by their margin (see [`Game::HAS_TERMINAL_VALUE`]) and games without
+/// [`Game::max_moves`], which are scored against [`UNBOUNDED_UPPER_BOUND`],
+/// have unknown distances.
 pub fn score_to_outcome<T: Game>(game: &T, score: isize) -> GameScoreOutcome {
+    let distance = |score: isize| {
+        game.max_moves()
+            .filter(|_| !T::HAS_TERMINAL_VALUE)
+            .and_then(|_| {
+                usize::try_from(score + upper_bound(game) - game.move_count() as isize).ok()
+            })
+    };
+
     match score.cmp(&0) {
-        Ordering::Greater => GameScoreOutcome::Win(
-            (-score + upper_bound(game) - game.move_count() as isize) as usize,
-        ),
+        Ordering::Greater => GameScoreOutcome::Win {
+            distance: distance(-score),
+        },
         Ordering::Equal => GameScoreOutcome::Tie,
-        Ordering::Less => GameScoreOutcome::Loss(
-            (score + upper_bound(game) - game.move_count() as isize) as usize,
-        ),
+        Ordering::Less => GameScoreOutcome::Loss {
+            distance: distance(score),
+        },
     }
 }
@@ -0,0 +1,70 @@
+//! [Killer moves](https://www.chessprogramming.org/Killer_Heuristic) for move ordering.
+
+/// The last move that caused a beta cutoff at every ply of a search.
+///
+/// Sibling positions often share good moves, so a move that refuted
+/// one position is worth trying first in the others at the same ply.
+pub(crate) struct KillerTable<M> {
+    killers: Vec<Option<M>>,
+}
+
+impl<M> Default for KillerTable<M> {
+    fn default() -> Self {
+        Self {
+            killers: Vec::new(),
+        }
+    }
+}
+
+impl<M: PartialEq> KillerTable<M> {
+    /// Remembers `m` as the move that caused a cutoff at `ply`.
+    pub(crate) fn store(&mut self, ply: usize, m: M) {
+        if self.killers.len() <= ply {
+            self.killers.resize_with(ply + 1, || None);
+        }
+
+        self.killers[ply] = Some(m);
+    }
+
+    /// Moves the killer move at `ply` to the front of `moves`, if it's one of them.
+    ///
+    /// The rest of the moves keep their order.
+    pub(crate) fn order(&self, ply: usize, moves: &mut [M]) {
+        let Some(Some(killer)) = self.killers.get(ply) else {
+            return;
+        };
+
+        if let Some(index) = moves.iter().position(|m| m == killer) {
+            moves[..=index].rotate_right(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn killer_goes_first() {
+        let mut killers = KillerTable::default();
+        let mut moves = [1, 2, 3, 4];
+
+        // nothing is stored yet
+        killers.order(2, &mut moves);
+        assert_eq!(moves, [1, 2, 3, 4]);
+
+        killers.store(2, 3);
+        killers.order(2, &mut moves);
+        assert_eq!(moves, [3, 1, 2, 4]);
+
+        // killers are only used at their own ply
+        let mut moves = [1, 2, 3, 4];
+        killers.order(1, &mut moves);
+        assert_eq!(moves, [1, 2, 3, 4]);
+
+        // and are ignored if they aren't legal
+        killers.store(1, 5);
+        killers.order(1, &mut moves);
+        assert_eq!(moves, [1, 2, 3, 4]);
+    }
+}
@@ -1,47 +1,152 @@
+//! Support for loopy games - games whose positions can repeat, so they could be played forever.
+//!
+//! A loopy game keeps a [`LoopyTracker`] of every position it has been in, and
+//! implements [`Loopy`] to describe itself without that history:
+//!
+//! 1. Store a `LoopyTracker<S, Self>` in the game, where `S` is the game without its tracker.
+//! 2. Call [`Loopy::mark_visited`] in `Game::make_move` before changing the position
+//!    (or [`LoopyTracker::clear`] after an irreversible move).
+//! 3. Return `GameState::Tie` from `Game::state` when [`Loopy::is_repetition_draw`] is true.
+//!
+//! Since a repeated position ends the game, every line of play is finite, and the solver
+//! scores endless play as a draw.
+//!
+//! ```
+//! use game_solver::loopy::{Loopy, LoopyTracker};
+//!
+//! /// A light that can only be switched on and off.
+//! #[derive(Clone, PartialEq, Eq, Hash)]
+//! struct Switch {
+//!     on: bool,
+//!     tracker: LoopyTracker<bool, Switch>,
+//! }
+//!
+//! impl Loopy<bool> for Switch {
+//!     fn tracker_mut(&mut self) -> &mut LoopyTracker<bool, Self> {
+//!         &mut self.tracker
+//!     }
+//!
+//!     fn tracker(&self) -> &LoopyTracker<bool, Self> {
+//!         &self.tracker
+//!     }
+//!
+//!     fn without_tracker(&self) -> bool {
+//!         self.on
+//!     }
+//! }
+//!
+//! let mut switch = Switch { on: false, tracker: LoopyTracker::new() };
+//! for _ in 0..2 {
+//!     switch.mark_visited();
+//!     switch.on = !switch.on;
+//! }
+//!
+//! assert!(switch.is_repetition_draw());
+//! ```
+//!
+//! See `tests/loopy.rs` for a full game.
+
 use std::fmt::Debug;
 use std::hash::Hash;
 use std::marker::PhantomData;
 
 use smallvec::SmallVec;
 
-/// We handle loopy games with a custom struct, `LoopyTracker`, which is a
-/// HashSet of some state T. This is used to keep track of the states that
-/// have been visited, and if a state has been visited, we can handle it appropriately.
-/// 
-/// `LoopyTracker` should be updated at `Game::make_move` and checked in `Game::state`.
-/// 
-/// We say `T` is the primary type, and `S` is some representation of `T` without the `LoopyTracker`.
-
+/// The positions a loopy game has been in, along with when repeating them draws the game.
+///
+/// We say `T` is the game, and `S` is some representation of `T` without the `LoopyTracker`.
+/// Only `S` is stored, so the tracker doesn't hold copies of itself.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct LoopyTracker<S: Eq + Hash, T: Eq + Hash> {
-    visited: SmallVec<[T; 3]>,
-    _phantom: PhantomData<S>,
+    visited: SmallVec<[S; 3]>,
+    /// The amount of times a position has to occur for the game to be a draw.
+    repetition_limit: usize,
+    _phantom: PhantomData<T>,
 }
 
-pub trait Loopy<S: Hash + Eq> where Self: Eq + Hash + Sized {
+/// A game that keeps track of the positions it has been in.
+pub trait Loopy<S: Hash + Eq>
+where
+    Self: Eq + Hash + Sized,
+{
     fn tracker_mut(&mut self) -> &mut LoopyTracker<S, Self>;
     fn tracker(&self) -> &LoopyTracker<S, Self>;
 
+    /// This position, without its history.
+    ///
+    /// Positions are compared by this, so it should hold everything that decides
+    /// how the game continues - including whose turn it is.
     fn without_tracker(&self) -> S;
+
+    /// Marks this position as visited.
+    ///
+    /// This should be called in `Game::make_move`, before the move changes the position.
+    fn mark_visited(&mut self) {
+        let state = self.without_tracker();
+        self.tracker_mut().visited.push(state);
+    }
+
+    /// The amount of times this position has been visited before.
+    fn visit_count(&self) -> usize {
+        self.tracker().visit_count(self)
+    }
+
+    /// Whether this position has occurred often enough to draw the game.
+    ///
+    /// This should be checked in `Game::state`, which should return `GameState::Tie` if it's true.
+    fn is_repetition_draw(&self) -> bool {
+        self.tracker().is_repetition_draw(self)
+    }
 }
 
 impl<S: Eq + Hash, T: Eq + Hash + Loopy<S>> LoopyTracker<S, T> {
-    /// Create a new `LoopyTracker`.
+    /// Create a new `LoopyTracker`, where the game is a draw as soon as a position is repeated.
     pub fn new() -> Self {
+        Self::with_repetition_limit(2)
+    }
+
+    /// Create a new `LoopyTracker`, where the game is a draw once a position has occurred
+    /// `repetition_limit` times - for example, 3 for a draw by threefold repetition.
+    pub fn with_repetition_limit(repetition_limit: usize) -> Self {
         Self {
             visited: SmallVec::new(),
+            repetition_limit,
             _phantom: PhantomData,
         }
     }
 
+    /// The amount of times a position has to occur for the game to be a draw.
+    pub fn repetition_limit(&self) -> usize {
+        self.repetition_limit
+    }
+
     /// Check if a state has been visited.
     pub fn has_visited(&self, state: &T) -> bool {
-        self.visited.contains(&state)
+        self.visit_count(state) > 0
+    }
+
+    /// The amount of times a state has been visited.
+    ///
+    /// States are compared without their trackers,
+    /// since the tracker of a repeated state holds more history.
+    pub fn visit_count(&self, state: &T) -> usize {
+        let state = state.without_tracker();
+
+        self.visited
+            .iter()
+            .filter(|&visited| visited == &state)
+            .count()
+    }
+
+    /// Whether `state` has occurred often enough to draw the game,
+    /// counting `state` itself as one occurrence.
+    pub fn is_repetition_draw(&self, state: &T) -> bool {
+        self.visit_count(state) + 1 >= self.repetition_limit
     }
 
     /// Mark a state as visited.
-    pub fn mark_visited(&mut self, state: T) {
-        self.visited.push(state);
+    pub fn mark_visited(&mut self, state: &T) {
+        self.visited.push(state.without_tracker());
     }
 
     /// The number of states visited.
@@ -69,3 +174,72 @@ impl<S: Eq + Hash, T: Eq + Hash + Loopy<S>> Default for LoopyTracker<S, T> {
 //         }
 //     }
 // }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A light that can only be switched on and off.
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    struct Switch {
+        on: bool,
+        tracker: Box<LoopyTracker<bool, Switch>>,
+    }
+
+    impl Loopy<bool> for Switch {
+        fn tracker_mut(&mut self) -> &mut LoopyTracker<bool, Self> {
+            &mut self.tracker
+        }
+
+        fn tracker(&self) -> &LoopyTracker<bool, Self> {
+            &self.tracker
+        }
+
+        fn without_tracker(&self) -> bool {
+            self.on
+        }
+    }
+
+    impl Switch {
+        fn flip(&mut self) {
+            self.mark_visited();
+            self.on = !self.on;
+        }
+    }
+
+    #[test]
+    fn threefold_repetition() {
+        let mut switch = Switch {
+            on: false,
+            tracker: Box::new(LoopyTracker::with_repetition_limit(3)),
+        };
+        assert_eq!(switch.visit_count(), 0);
+
+        switch.flip();
+        switch.flip();
+        // off for the second time
+        assert_eq!(switch.visit_count(), 1);
+        assert!(!switch.is_repetition_draw());
+
+        switch.flip();
+        assert!(!switch.is_repetition_draw());
+        switch.flip();
+        // and the third
+        assert_eq!(switch.visit_count(), 2);
+        assert!(switch.is_repetition_draw());
+    }
+
+    #[test]
+    fn repeating_draws_by_default() {
+        let mut switch = Switch {
+            on: false,
+            tracker: Box::new(LoopyTracker::new()),
+        };
+        assert!(!switch.is_repetition_draw());
+
+        switch.flip();
+        assert!(!switch.is_repetition_draw());
+        switch.flip();
+        assert!(switch.is_repetition_draw());
+    }
+}
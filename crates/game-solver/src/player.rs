@@ -1,8 +1,11 @@
 /// Represents a player.
-pub trait Player: Sized + Eq {
+pub trait Player: Sized + Eq + std::fmt::Debug {
     /// The max player count.
     #[must_use]
     fn count() -> usize;
+    /// Every player, in order of their [`Player::idx`].
+    #[must_use]
+    fn all() -> Vec<Self>;
     /// The current index of this player starting at 0.
     #[must_use]
     fn idx(&self) -> usize;
@@ -50,6 +53,10 @@ impl Player for PartizanPlayer {
         2
     }
 
+    fn all() -> Vec<Self> {
+        vec![Self::Left, Self::Right]
+    }
+
     fn idx(&self) -> usize {
         match self {
             Self::Left => 0,
@@ -90,8 +97,15 @@ pub enum ImpartialPlayer {
 }
 
 impl ImpartialPlayer {
+    /// The player who was to move when the game had made `initial_move_count` moves,
+    /// as seen from the game after `final_move_count` moves.
+    ///
+    /// Players alternate every move, so this is [`ImpartialPlayer::Next`] if an even amount of moves
+    /// were made in between, and [`ImpartialPlayer::Previous`] otherwise.
+    /// This lets a winner reported by [`Game::state`](crate::game::Game::state) deep in a search
+    /// be compared to the player who started the search.
     pub fn from_move_count(initial_move_count: usize, final_move_count: usize) -> ImpartialPlayer {
-        if (final_move_count - initial_move_count) % 2 == 0 {
+        if final_move_count.abs_diff(initial_move_count) % 2 == 0 {
             ImpartialPlayer::Next
         } else {
             ImpartialPlayer::Previous
@@ -104,6 +118,10 @@ impl Player for ImpartialPlayer {
         2
     }
 
+    fn all() -> Vec<Self> {
+        vec![Self::Next, Self::Previous]
+    }
+
     fn idx(&self) -> usize {
         match self {
             Self::Next => 0,
@@ -130,7 +148,7 @@ impl Player for ImpartialPlayer {
 impl TwoPlayer for ImpartialPlayer {}
 
 /// Represents a player in an N-player game.
-#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy, Hash)]
 pub struct NPlayerPartizanConst<const N: usize>(usize);
 
 impl<const N: usize> NPlayerPartizanConst<N> {
@@ -150,6 +168,10 @@ impl<const N: usize> Player for NPlayerPartizanConst<N> {
         N
     }
 
+    fn all() -> Vec<Self> {
+        (0..N).map(Self::new_unchecked).collect()
+    }
+
     fn idx(&self) -> usize {
         self.0
     }
@@ -171,3 +193,51 @@ impl<const N: usize> Player for NPlayerPartizanConst<N> {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn all_players() {
+        let players = NPlayerPartizanConst::<4>::all();
+        assert_eq!(players.len(), 4);
+        assert_eq!(players.iter().collect::<HashSet<_>>().len(), 4);
+        assert!(players
+            .iter()
+            .enumerate()
+            .all(|(index, player)| player.idx() == index));
+
+        assert_eq!(
+            PartizanPlayer::all(),
+            vec![PartizanPlayer::Left, PartizanPlayer::Right]
+        );
+        assert_eq!(ImpartialPlayer::all().len(), ImpartialPlayer::count());
+    }
+
+    #[test]
+    fn impartial_player_from_move_count() {
+        assert_eq!(
+            ImpartialPlayer::from_move_count(0, 0),
+            ImpartialPlayer::Next
+        );
+        assert_eq!(
+            ImpartialPlayer::from_move_count(0, 1),
+            ImpartialPlayer::Previous
+        );
+        assert_eq!(
+            ImpartialPlayer::from_move_count(3, 7),
+            ImpartialPlayer::Next
+        );
+        assert_eq!(
+            ImpartialPlayer::from_move_count(3, 8),
+            ImpartialPlayer::Previous
+        );
+
+        // turning the player once per move made gets to the same player
+        let player = (5..10).fold(ImpartialPlayer::Next, |player, _| player.turn());
+        assert_eq!(ImpartialPlayer::from_move_count(5, 10), player);
+    }
+}
@@ -1,4 +1,8 @@
-use std::sync::atomic::{AtomicU64, AtomicUsize};
+use std::{
+    fmt::{Debug, Formatter},
+    num::NonZeroU64,
+    sync::atomic::{AtomicU64, AtomicUsize, Ordering},
+};
 
 use crate::player::Player;
 
@@ -19,6 +23,27 @@ impl Default for TerminalEnds {
     }
 }
 
+/// A callback that the solver calls with the current stats every `interval` explored states.
+///
+/// The callback has to be `Send + Sync`, as parallel solvers (like `par_solve` and `par_move_scores`)
+/// share the same stats between threads - so it may be called from any of their threads,
+/// even at the same time. Since it runs on the solving thread, it should return quickly,
+/// such as by sending the stats it needs to another thread.
+pub struct Progress<P: Player> {
+    interval: NonZeroU64,
+    callback: ProgressCallback<P>,
+}
+
+type ProgressCallback<P> = Box<dyn Fn(&Stats<P>) + Send + Sync>;
+
+impl<P: Player> Debug for Progress<P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Progress")
+            .field("interval", &self.interval)
+            .finish_non_exhaustive()
+    }
+}
+
 #[derive(Debug)]
 pub struct Stats<P: Player> {
     pub states_explored: AtomicU64,
@@ -28,4 +53,177 @@ pub struct Stats<P: Player> {
     pub terminal_ends: TerminalEnds,
     pub original_player: P,
     pub original_move_count: usize,
+    progress: Option<Progress<P>>,
+}
+
+impl<P: Player> Stats<P> {
+    /// Creates empty stats for solving a game
+    /// whose current player and move count are given.
+    pub fn new(original_player: P, original_move_count: usize) -> Self {
+        Self {
+            states_explored: AtomicU64::new(0),
+            max_depth: AtomicUsize::new(0),
+            cache_hits: AtomicU64::new(0),
+            pruning_cutoffs: AtomicU64::new(0),
+            terminal_ends: TerminalEnds::default(),
+            original_player,
+            original_move_count,
+            progress: None,
+        }
+    }
+
+    /// Calls `callback` with these stats every `interval` explored states,
+    /// so solving progress can be shown without polling the stats.
+    ///
+    /// See [`Progress`] for the requirements of the callback.
+    pub fn with_progress(
+        mut self,
+        interval: NonZeroU64,
+        callback: impl Fn(&Stats<P>) + Send + Sync + 'static,
+    ) -> Self {
+        self.progress = Some(Progress {
+            interval,
+            callback: Box::new(callback),
+        });
+        self
+    }
+
+    /// Counts a newly explored state, reporting progress if it's due.
+    pub(crate) fn explore_state(&self) {
+        let states_explored = self.states_explored.fetch_add(1, Ordering::Relaxed) + 1;
+
+        if let Some(progress) = &self.progress {
+            if states_explored % progress.interval == 0 {
+                (progress.callback)(self);
+            }
+        }
+    }
+
+    /// The branching factor `b` of a uniform tree with [`Stats::max_depth`] levels
+    /// and as many nodes as [`Stats::states_explored`] - that is, `states_explored = b ^ max_depth`.
+    ///
+    /// The better the solver's move ordering, the smaller this is.
+    /// Returns 0 if nothing past the first move was explored.
+    pub fn effective_branching_factor(&self) -> f64 {
+        let states_explored = self.states_explored.load(Ordering::Relaxed) as f64;
+        let max_depth = self.max_depth.load(Ordering::Relaxed) as f64;
+
+        if max_depth == 0.0 {
+            return 0.0;
+        }
+
+        states_explored.powf(max_depth.recip())
+    }
+
+    /// The fraction of explored states that were resolved by the transposition table.
+    ///
+    /// Returns 0 if no states were explored.
+    pub fn cache_hit_rate(&self) -> f64 {
+        let states_explored = self.states_explored.load(Ordering::Relaxed);
+
+        if states_explored == 0 {
+            return 0.0;
+        }
+
+        self.cache_hits.load(Ordering::Relaxed) as f64 / states_explored as f64
+    }
+}
+
+/// Stats are serialized as a snapshot of their counters at the time of serialization,
+/// so they should only be serialized once solving has finished.
+#[cfg(feature = "serde")]
+impl<P: Player> serde::Serialize for Stats<P> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("Stats", 6)?;
+        state.serialize_field(
+            "states_explored",
+            &self.states_explored.load(Ordering::SeqCst),
+        )?;
+        state.serialize_field("max_depth", &self.max_depth.load(Ordering::SeqCst))?;
+        state.serialize_field("cache_hits", &self.cache_hits.load(Ordering::SeqCst))?;
+        state.serialize_field(
+            "pruning_cutoffs",
+            &self.pruning_cutoffs.load(Ordering::SeqCst),
+        )?;
+        state.serialize_field("terminal_ends", &self.terminal_ends)?;
+        state.serialize_field("original_move_count", &self.original_move_count)?;
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for TerminalEnds {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("TerminalEnds", 3)?;
+        state.serialize_field("winning", &self.winning.load(Ordering::SeqCst))?;
+        state.serialize_field("tie", &self.tie.load(Ordering::SeqCst))?;
+        state.serialize_field("losing", &self.losing.load(Ordering::SeqCst))?;
+        state.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::player::PartizanPlayer;
+
+    use super::*;
+
+    #[test]
+    fn progress_every_interval() {
+        let reports = Arc::new(AtomicU64::new(0));
+        let stats =
+            Stats::new(PartizanPlayer::Left, 0).with_progress(NonZeroU64::new(3).unwrap(), {
+                let reports = reports.clone();
+                move |stats| {
+                    assert_eq!(stats.states_explored.load(Ordering::Relaxed) % 3, 0);
+                    reports.fetch_add(1, Ordering::Relaxed);
+                }
+            });
+
+        for _ in 0..10 {
+            stats.explore_state();
+        }
+
+        assert_eq!(stats.states_explored.load(Ordering::Relaxed), 10);
+        assert_eq!(reports.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn derived_stats() {
+        let stats = Stats::new(PartizanPlayer::Left, 0);
+        assert_eq!(stats.effective_branching_factor(), 0.0);
+        assert_eq!(stats.cache_hit_rate(), 0.0);
+
+        stats.states_explored.store(1000, Ordering::Relaxed);
+        stats.max_depth.store(3, Ordering::Relaxed);
+        stats.cache_hits.store(250, Ordering::Relaxed);
+
+        assert!((stats.effective_branching_factor() - 10.0).abs() < 1e-9);
+        assert_eq!(stats.cache_hit_rate(), 0.25);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize() {
+        let stats = Stats::new(PartizanPlayer::Left, 2);
+        stats.states_explored.store(10, Ordering::Relaxed);
+        stats.terminal_ends.tie.store(3, Ordering::Relaxed);
+
+        let expected = Stats::new(PartizanPlayer::Right, 2);
+        expected.states_explored.store(10, Ordering::Relaxed);
+        expected.terminal_ends.tie.store(3, Ordering::Relaxed);
+
+        let bytes = bincode::serialize(&stats).unwrap();
+        assert_eq!(bytes, bincode::serialize(&expected).unwrap());
+        assert_ne!(
+            bytes,
+            bincode::serialize(&Stats::new(PartizanPlayer::Left, 2)).unwrap()
+        );
+    }
 }
@@ -5,15 +5,20 @@ use moka::future::Cache;
 #[cfg(feature = "rayon")]
 use std::sync::Arc;
 
-use crate::game::Game;
-
 use std::{
-    collections::HashMap,
+    cell::Cell,
+    collections::{hash_map::RandomState, HashMap},
     hash::{BuildHasher, Hash},
 };
 
+#[cfg(feature = "serde")]
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+#[cfg(feature = "serde")]
+use std::io::{Read, Write};
+
 /// A score in a transposition table.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Score {
     /// The lower bound of the score.
     /// This generally doesn't bring too much benefit,
@@ -22,21 +27,42 @@ pub enum Score {
     /// The upper bound of the score,
     /// which helps get rid of many useless branches.
     UpperBound(isize),
+    /// The exact score, which lets the solver skip searching this board entirely.
+    Exact(isize),
 }
 
-/// A memoization strategy for a perfect-information sequential game.
-pub trait TranspositionTable<T: Eq + Hash + Game> {
+/// A memoization strategy for a perfect-information sequential game,
+/// which stores boards by their [`Game::key`](crate::game::Game::key).
+pub trait TranspositionTable<K: Eq + Hash> {
     /// Get the score of a board, if it exists.
-    fn get(&self, board: &T) -> Option<Score>;
+    fn get(&self, board: &K) -> Option<Score>;
 
     /// Insert a board into the transposition table.
-    fn insert(&mut self, board: T, score: Score);
+    fn insert(&mut self, board: K, score: Score);
 
     /// Returns true if the board is in the transposition table.
-    fn has(&self, board: &T) -> bool;
+    fn has(&self, board: &K) -> bool;
+
+    /// Removes every board from the transposition table,
+    /// so it can be reused for an unrelated game.
+    fn clear(&mut self);
+
+    /// The amount of boards in the transposition table.
+    fn len(&self) -> usize;
+
+    /// Returns true if there are no boards in the transposition table.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Marks the start of another search in the same game, such as after a move was played,
+    /// so tables that age their boards (like [`AgingTable`]) can forget the ones from earlier searches.
+    ///
+    /// Does nothing by default.
+    fn advance_age(&mut self) {}
 }
 
-impl<K: Eq + Hash + Game, S: BuildHasher + Default> TranspositionTable<K> for HashMap<K, Score, S> {
+impl<K: Eq + Hash, S: BuildHasher + Default> TranspositionTable<K> for HashMap<K, Score, S> {
     fn get(&self, board: &K) -> Option<Score> {
         self.get(board).copied()
     }
@@ -48,28 +74,237 @@ impl<K: Eq + Hash + Game, S: BuildHasher + Default> TranspositionTable<K> for Ha
     fn has(&self, board: &K) -> bool {
         self.contains_key(board)
     }
+
+    fn clear(&mut self) {
+        self.clear();
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+struct BoundedSlot<K> {
+    board: K,
+    score: Score,
+    /// Whether this slot was used since the clock hand last passed it.
+    referenced: Cell<bool>,
+}
+
+/// A transposition table that holds at most a fixed amount of boards.
+///
+/// Once full, boards are evicted with the
+/// [clock (second-chance)](https://en.wikipedia.org/wiki/Page_replacement_algorithm#Clock)
+/// algorithm: boards that were looked up since the last eviction pass get a second chance.
+///
+/// Unlike [`TranspositionCache`], this doesn't require the `rayon` feature,
+/// but it can't be shared between threads.
+pub struct BoundedTable<K, S = RandomState> {
+    slots: Vec<BoundedSlot<K>>,
+    index: HashMap<K, usize, S>,
+    hand: usize,
+    capacity: usize,
+}
+
+impl<K: Eq + Hash + Clone, S: BuildHasher + Default> BoundedTable<K, S> {
+    /// Create a new bounded table that holds at most `capacity` boards.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            slots: Vec::with_capacity(capacity),
+            index: HashMap::with_capacity_and_hasher(capacity, S::default()),
+            hand: 0,
+            capacity,
+        }
+    }
+
+    /// The amount of boards in the table.
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns true if there are no boards in the table.
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    /// The maximum amount of boards the table can hold.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+impl<K: Eq + Hash + Clone, S: BuildHasher + Default> TranspositionTable<K> for BoundedTable<K, S> {
+    fn get(&self, board: &K) -> Option<Score> {
+        self.index.get(board).map(|&idx| {
+            let slot = &self.slots[idx];
+            slot.referenced.set(true);
+            slot.score
+        })
+    }
+
+    fn insert(&mut self, board: K, score: Score) {
+        if let Some(&idx) = self.index.get(&board) {
+            let slot = &mut self.slots[idx];
+            slot.score = score;
+            slot.referenced.set(true);
+            return;
+        }
+
+        if self.capacity == 0 {
+            return;
+        }
+
+        let slot = BoundedSlot {
+            board: board.clone(),
+            score,
+            referenced: Cell::new(false),
+        };
+
+        if self.slots.len() < self.capacity {
+            self.index.insert(board, self.slots.len());
+            self.slots.push(slot);
+            return;
+        }
+
+        // advance the clock hand until we find a slot without a second chance
+        while self.slots[self.hand].referenced.replace(false) {
+            self.hand = (self.hand + 1) % self.capacity;
+        }
+
+        let evicted = std::mem::replace(&mut self.slots[self.hand], slot);
+        self.index.remove(&evicted.board);
+        self.index.insert(board, self.hand);
+        self.hand = (self.hand + 1) % self.capacity;
+    }
+
+    fn has(&self, board: &K) -> bool {
+        self.index.contains_key(board)
+    }
+
+    fn clear(&mut self) {
+        self.slots.clear();
+        self.index.clear();
+        self.hand = 0;
+    }
+
+    fn len(&self) -> usize {
+        self.slots.len()
+    }
+}
+
+struct AgedEntry {
+    score: Score,
+    /// The age of the table when this board was inserted.
+    age: usize,
+}
+
+/// A transposition table that forgets boards once they're too old,
+/// for reusing a table across the moves of a game being played.
+///
+/// Every call to [`TranspositionTable::advance_age`] ages the table by one,
+/// and drops the boards inserted more than `max_age` ages ago.
+/// Looking a board up doesn't make it younger, but inserting it again does.
+pub struct AgingTable<K, S = RandomState> {
+    entries: HashMap<K, AgedEntry, S>,
+    age: usize,
+    max_age: usize,
+}
+
+impl<K: Eq + Hash, S: BuildHasher + Default> AgingTable<K, S> {
+    /// Create a new table that forgets boards after `max_age` calls to
+    /// [`TranspositionTable::advance_age`].
+    pub fn new(max_age: usize) -> Self {
+        Self {
+            entries: HashMap::default(),
+            age: 0,
+            max_age,
+        }
+    }
+
+    /// How many times this table has aged.
+    pub fn age(&self) -> usize {
+        self.age
+    }
+}
+
+impl<K: Eq + Hash, S: BuildHasher + Default> TranspositionTable<K> for AgingTable<K, S> {
+    fn get(&self, board: &K) -> Option<Score> {
+        self.entries.get(board).map(|entry| entry.score)
+    }
+
+    fn insert(&mut self, board: K, score: Score) {
+        let age = self.age;
+        self.entries.insert(board, AgedEntry { score, age });
+    }
+
+    fn has(&self, board: &K) -> bool {
+        self.entries.contains_key(board)
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn advance_age(&mut self) {
+        self.age += 1;
+
+        let (age, max_age) = (self.age, self.max_age);
+        self.entries.retain(|_, entry| age - entry.age <= max_age);
+    }
+}
+
+/// Saves a transposition table to `writer`, so a long solve can be resumed later with [`load_from`].
+///
+/// Boards are stored by their serialized representation,
+/// so the serialized form of a board must stay stable across runs for the
+/// loaded table to be valid: changing the board's fields invalidates saved tables.
+///
+/// This requires the `serde` feature to be enabled.
+#[cfg(feature = "serde")]
+pub fn save_to<K: Eq + Hash + Serialize, S: BuildHasher, W: Write>(
+    table: &HashMap<K, Score, S>,
+    writer: W,
+) -> bincode::Result<()> {
+    bincode::serialize_into(writer, table)
+}
+
+/// Loads a transposition table saved by [`save_to`] from `reader`.
+///
+/// This requires the `serde` feature to be enabled.
+#[cfg(feature = "serde")]
+pub fn load_from<K: Eq + Hash + DeserializeOwned, S: BuildHasher + Default, R: Read>(
+    reader: R,
+) -> bincode::Result<HashMap<K, Score, S>> {
+    bincode::deserialize_from(reader)
 }
 
 /// Powerful transposition table that uses an underlying concurrent
 /// [LFU](https://en.wikipedia.org/wiki/Least_frequently_used) cache,
 /// powered by [moka](https://github.com/moka-rs/moka).
 #[cfg(feature = "rayon")]
-pub struct TranspositionCache<K: Eq + Hash + Game + Send + Sync + 'static, S: BuildHasher + Default>(
+pub struct TranspositionCache<K: Eq + Hash + Send + Sync + 'static, S: BuildHasher + Default>(
     Cache<K, Score, S>,
 );
 
 #[cfg(feature = "rayon")]
-impl<
-        K: Eq + Hash + Game + Send + Sync,
-        S: BuildHasher + Default + Send + Sync + Clone + 'static,
-    > TranspositionCache<K, S>
+impl<K: Eq + Hash + Send + Sync, S: BuildHasher + Default + Send + Sync + Clone + 'static>
+    TranspositionCache<K, S>
 {
     /// Create a new transposition cache with the given capacity and hasher.
     pub fn with_capacity(capacity: u64) -> Self {
+        Self::with_capacity_and_hasher(capacity, S::default())
+    }
+
+    /// Create a new transposition cache with the given capacity, using `hasher` to hash games.
+    pub fn with_capacity_and_hasher(capacity: u64, hasher: S) -> Self {
         Self(
             Cache::builder()
                 .max_capacity(capacity)
-                .build_with_hasher(S::default()),
+                .build_with_hasher(hasher),
         )
     }
 
@@ -77,21 +312,43 @@ impl<
     /// an estimated three fourths of the remaining memory.
     #[must_use]
     pub fn new() -> Self {
+        Self::with_hasher(S::default())
+    }
+
+    /// Create a new transposition cache with an estimated three fourths
+    /// of the remaining memory, using `hasher` to hash games.
+    pub fn with_hasher(hasher: S) -> Self {
         let score_size = std::mem::size_of::<Score>() as u64;
 
-        Self::with_capacity(
+        Self::with_capacity_and_hasher(
             // get three fourths of the memory, and divide that by the size of a score
             // to get the number of scores that can fit in the cache
             (sysinfo::System::new_all().total_memory() * 3 / 4) / score_size,
+            hasher,
         )
     }
 }
 
+/// Builds [`XxHash64`](twox_hash::XxHash64) hashers that all start from the same seed,
+/// so games hash the same way in every run - unlike `RandomXxHashBuilder64`.
+///
+/// This requires the `xxhash` feature to be enabled.
+#[cfg(feature = "xxhash")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SeededXxHashBuilder64(pub u64);
+
+#[cfg(feature = "xxhash")]
+impl BuildHasher for SeededXxHashBuilder64 {
+    type Hasher = twox_hash::XxHash64;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        twox_hash::XxHash64::with_seed(self.0)
+    }
+}
+
 #[cfg(feature = "rayon")]
-impl<
-        K: Eq + Hash + Game + Send + Sync,
-        S: BuildHasher + Default + Send + Sync + Clone + 'static,
-    > Default for TranspositionCache<K, S>
+impl<K: Eq + Hash + Send + Sync, S: BuildHasher + Default + Send + Sync + Clone + 'static> Default
+    for TranspositionCache<K, S>
 {
     fn default() -> Self {
         Self::new()
@@ -100,7 +357,7 @@ impl<
 
 #[cfg(feature = "rayon")]
 impl<
-        K: Eq + Hash + Game + Send + Sync + 'static,
+        K: Eq + Hash + Send + Sync + 'static,
         S: BuildHasher + Default + Send + Sync + Clone + 'static,
     > TranspositionTable<K> for Arc<TranspositionCache<K, S>>
 {
@@ -115,4 +372,166 @@ impl<
     fn has(&self, board: &K) -> bool {
         self.0.contains_key(board)
     }
+
+    fn clear(&mut self) {
+        self.0.invalidate_all();
+        futures::executor::block_on(self.0.run_pending_tasks());
+    }
+
+    /// Since moka evicts boards lazily, this runs pending evictions first.
+    fn len(&self) -> usize {
+        futures::executor::block_on(self.0.run_pending_tasks());
+        self.0.entry_count() as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        game::{Game, GameState, Normal},
+        player::ImpartialPlayer,
+    };
+
+    /// A subtraction game where players take one or two from a counter.
+    #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+    struct Countdown(usize);
+
+    impl Normal for Countdown {}
+    impl Game for Countdown {
+        type Move = usize;
+        type Iter<'a> = std::vec::IntoIter<usize>;
+        type MoveError = std::convert::Infallible;
+        type Player = ImpartialPlayer;
+        type Key = Self;
+
+        fn move_count(&self) -> usize {
+            0
+        }
+
+        fn max_moves(&self) -> Option<usize> {
+            Some(self.0)
+        }
+
+        fn make_move(&mut self, m: &usize) -> Result<(), Self::MoveError> {
+            self.0 -= m;
+            Ok(())
+        }
+
+        fn possible_moves(&self) -> Self::Iter<'_> {
+            (1..=self.0.min(2)).collect::<Vec<_>>().into_iter()
+        }
+
+        fn state(&self) -> GameState<Self::Player> {
+            <Self as Normal>::state(self)
+        }
+
+        fn player(&self) -> Self::Player {
+            ImpartialPlayer::Next
+        }
+    }
+
+    #[test]
+    fn bounded_table_evicts() {
+        let mut table = BoundedTable::<Countdown>::with_capacity(2);
+        table.insert(Countdown(1), Score::Exact(1));
+        table.insert(Countdown(2), Score::Exact(2));
+        assert_eq!(table.len(), 2);
+
+        // give the first board a second chance, so the second one is evicted
+        assert_eq!(table.get(&Countdown(1)), Some(Score::Exact(1)));
+        table.insert(Countdown(3), Score::Exact(3));
+
+        assert_eq!(table.len(), 2);
+        assert!(table.has(&Countdown(1)));
+        assert!(!table.has(&Countdown(2)));
+        assert!(table.has(&Countdown(3)));
+    }
+
+    #[test]
+    fn aging_table_forgets_old_boards() {
+        let mut table = AgingTable::<Countdown>::new(1);
+        table.insert(Countdown(1), Score::Exact(1));
+        table.advance_age();
+        table.insert(Countdown(2), Score::Exact(2));
+
+        // the first board is one age old, which is still young enough
+        assert_eq!(table.get(&Countdown(1)), Some(Score::Exact(1)));
+        assert_eq!(table.len(), 2);
+
+        table.advance_age();
+        assert_eq!(table.age(), 2);
+        assert_eq!(table.get(&Countdown(1)), None);
+        assert!(!table.has(&Countdown(1)));
+        assert_eq!(table.get(&Countdown(2)), Some(Score::Exact(2)));
+        assert_eq!(table.len(), 1);
+
+        // boards inserted again start over
+        table.insert(Countdown(1), Score::Exact(1));
+        table.advance_age();
+        assert!(table.has(&Countdown(1)));
+        assert!(!table.has(&Countdown(2)));
+    }
+
+    #[test]
+    fn bounded_table_solves() {
+        let game = Countdown(20);
+        assert_eq!(
+            crate::solve(
+                &game,
+                &mut BoundedTable::<Countdown>::with_capacity(4),
+                crate::SolveMode::Strong,
+                None
+            )
+            .unwrap(),
+            crate::solve(&game, &mut HashMap::new(), crate::SolveMode::Strong, None).unwrap()
+        );
+    }
+
+    /// Solves a game twice with `table`, clearing it in between,
+    /// and returns the cache hits of every solve.
+    fn cache_hits_around_clear(mut table: impl TranspositionTable<Countdown>) -> [u64; 3] {
+        let game = Countdown(12);
+        let solve = |table: &mut dyn TranspositionTable<Countdown>| {
+            let stats = crate::stats::Stats::new(game.player(), game.move_count());
+            crate::solve(&game, table, crate::SolveMode::Strong, Some(&stats)).unwrap();
+            stats.cache_hits.load(std::sync::atomic::Ordering::Relaxed)
+        };
+
+        let first = solve(&mut table);
+        assert!(!table.is_empty());
+        let warm = solve(&mut table);
+
+        table.clear();
+        assert_eq!(table.len(), 0);
+        let cleared = solve(&mut table);
+
+        [first, warm, cleared]
+    }
+
+    #[test]
+    fn clearing_resets_cache_hits() {
+        for [first, warm, cleared] in [
+            cache_hits_around_clear(HashMap::new()),
+            cache_hits_around_clear(BoundedTable::<Countdown>::with_capacity(64)),
+            cache_hits_around_clear(AgingTable::<Countdown>::new(0)),
+        ] {
+            // a warm table answers the root straight away
+            assert!(warm < first);
+            assert_eq!(cleared, first);
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn cache_clears() {
+        use std::collections::hash_map::RandomState;
+
+        let [first, warm, cleared] = cache_hits_around_clear(Arc::new(TranspositionCache::<
+            Countdown,
+            RandomState,
+        >::with_capacity(64)));
+        assert!(warm < first);
+        assert_eq!(cleared, first);
+    }
 }
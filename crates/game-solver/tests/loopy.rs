@@ -0,0 +1,196 @@
+//! A pursuit game, where a cop chases a robber around a graph.
+//!
+//! Nothing stops the robber from running in circles forever, so the game
+//! relies on [`LoopyTracker`] to end it in a draw once a position repeats.
+
+use std::collections::HashMap;
+
+use game_solver::{
+    game::{Game, GameState},
+    loopy::{Loopy, LoopyTracker},
+    player::{PartizanPlayer, Player},
+    solve, SolveMode,
+};
+use thiserror::Error;
+
+/// Everything about a [`Pursuit`] besides its history.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct Position {
+    cop: usize,
+    robber: usize,
+    player: PartizanPlayer,
+}
+
+/// The cop (left) and the robber (right) take turns moving to an adjacent vertex.
+///
+/// The cop wins by moving onto the robber, and the robber can't move onto the cop,
+/// so a robber with nowhere to go loses.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct Pursuit {
+    /// The neighbours of every vertex.
+    graph: Vec<Vec<usize>>,
+    cop: usize,
+    robber: usize,
+    move_count: usize,
+    tracker: LoopyTracker<Position, Pursuit>,
+}
+
+#[derive(Error, Debug, Clone)]
+#[error("vertex {0} isn't adjacent")]
+struct NotAdjacent(usize);
+
+impl Pursuit {
+    fn new(graph: Vec<Vec<usize>>, cop: usize, robber: usize) -> Self {
+        Self {
+            graph,
+            cop,
+            robber,
+            move_count: 0,
+            tracker: LoopyTracker::new(),
+        }
+    }
+
+    /// A cycle of `length` vertices.
+    fn cycle(length: usize, cop: usize, robber: usize) -> Self {
+        let graph = (0..length)
+            .map(|vertex| vec![(vertex + length - 1) % length, (vertex + 1) % length])
+            .collect();
+
+        Self::new(graph, cop, robber)
+    }
+
+    /// A path of `length` vertices.
+    fn path(length: usize, cop: usize, robber: usize) -> Self {
+        let graph = (0..length)
+            .map(|vertex| {
+                [
+                    vertex.checked_sub(1),
+                    Some(vertex + 1).filter(|&v| v < length),
+                ]
+                .into_iter()
+                .flatten()
+                .collect()
+            })
+            .collect();
+
+        Self::new(graph, cop, robber)
+    }
+}
+
+impl Loopy<Position> for Pursuit {
+    fn tracker_mut(&mut self) -> &mut LoopyTracker<Position, Self> {
+        &mut self.tracker
+    }
+
+    fn tracker(&self) -> &LoopyTracker<Position, Self> {
+        &self.tracker
+    }
+
+    fn without_tracker(&self) -> Position {
+        Position {
+            cop: self.cop,
+            robber: self.robber,
+            player: self.player(),
+        }
+    }
+}
+
+impl Game for Pursuit {
+    type Move = usize;
+    type Iter<'a> = std::vec::IntoIter<usize>;
+    type Player = PartizanPlayer;
+    type Key = Self;
+    type MoveError = NotAdjacent;
+
+    fn move_count(&self) -> usize {
+        self.move_count
+    }
+
+    fn max_moves(&self) -> Option<usize> {
+        None
+    }
+
+    fn make_move(&mut self, m: &usize) -> Result<(), NotAdjacent> {
+        if !self.possible_moves().any(|vertex| vertex == *m) {
+            return Err(NotAdjacent(*m));
+        }
+
+        self.mark_visited();
+        match self.player() {
+            PartizanPlayer::Left => self.cop = *m,
+            PartizanPlayer::Right => self.robber = *m,
+        }
+        self.move_count += 1;
+
+        Ok(())
+    }
+
+    fn possible_moves(&self) -> Self::Iter<'_> {
+        match self.player() {
+            PartizanPlayer::Left => self.graph[self.cop].clone(),
+            PartizanPlayer::Right => self.graph[self.robber]
+                .iter()
+                .copied()
+                .filter(|&vertex| vertex != self.cop)
+                .collect(),
+        }
+        .into_iter()
+    }
+
+    fn state(&self) -> GameState<PartizanPlayer> {
+        if self.cop == self.robber {
+            GameState::Win(PartizanPlayer::Left)
+        } else if self.is_repetition_draw() {
+            GameState::Tie
+        } else if self.possible_moves().next().is_none() {
+            GameState::Win(self.player().previous())
+        } else {
+            GameState::Playable
+        }
+    }
+
+    fn player(&self) -> PartizanPlayer {
+        if self.move_count % 2 == 0 {
+            PartizanPlayer::Left
+        } else {
+            PartizanPlayer::Right
+        }
+    }
+}
+
+#[test]
+fn robber_escapes_a_cycle() {
+    let game = Pursuit::cycle(4, 0, 2);
+    assert_eq!(
+        solve(&game, &mut HashMap::new(), SolveMode::Strong, None).unwrap(),
+        0
+    );
+}
+
+#[test]
+fn robber_is_cornered_on_a_path() {
+    let game = Pursuit::path(3, 0, 2);
+    assert!(solve(&game, &mut HashMap::new(), SolveMode::Strong, None).unwrap() > 0);
+
+    // a longer path only delays it
+    let game = Pursuit::path(6, 0, 3);
+    assert!(solve(&game, &mut HashMap::new(), SolveMode::Strong, None).unwrap() > 0);
+}
+
+#[test]
+fn running_in_circles_repeats() {
+    let mut game = Pursuit::cycle(4, 0, 2);
+    let start = game.clone();
+
+    for m in [1, 3, 0] {
+        game.make_move(&m).unwrap();
+        assert_eq!(game.state(), GameState::Playable);
+    }
+    assert!(game.tracker().has_visited(&Pursuit::cycle(4, 1, 3)));
+    assert!(!game.tracker().has_visited(&game));
+
+    // the robber returns to where the chase started
+    game.make_move(&2).unwrap();
+    assert!(game.tracker().has_visited(&start));
+    assert_eq!(game.state(), GameState::Tie);
+}
@@ -0,0 +1,136 @@
+use game_solver::{
+    game::Game, par_move_scores, par_move_scores_seeded, player::TwoPlayer, stats::Stats,
+    GameSolveError,
+};
+use std::{
+    hash::Hash,
+    sync::{atomic::Ordering, Arc},
+    time::{Duration, Instant},
+};
+
+/// How long it took to score every move of a game once.
+#[derive(Debug, Clone, Copy)]
+pub struct BenchRun {
+    pub states_explored: u64,
+    pub duration: Duration,
+}
+
+impl BenchRun {
+    pub fn states_per_second(&self) -> f64 {
+        self.states_explored as f64 / self.duration.as_secs_f64()
+    }
+}
+
+/// Scores every move of `game` `iterations` times, each with an empty transposition table.
+///
+/// If `seed` is set, the transposition tables hash games deterministically from it.
+pub async fn bench_runs<
+    T: Game<Player = impl TwoPlayer + Sync + Send + 'static> + Eq + Hash + Sync + Send + 'static,
+>(
+    game: &T,
+    iterations: usize,
+    seed: Option<u64>,
+) -> Result<Vec<BenchRun>, GameSolveError<T>>
+where
+    T::Move: Sync + Send,
+    T::MoveError: Sync + Send,
+    T::Key: Sync + Send,
+{
+    let mut runs = Vec::with_capacity(iterations);
+
+    for _ in 0..iterations {
+        let stats = Arc::new(Stats::new(game.player(), game.move_count()));
+
+        let start = Instant::now();
+        match seed {
+            Some(seed) => par_move_scores_seeded(game, seed, Some(stats.clone()), None).await?,
+            None => par_move_scores(game, Some(stats.clone()), None).await?,
+        };
+        let duration = start.elapsed();
+
+        runs.push(BenchRun {
+            states_explored: stats.states_explored.load(Ordering::SeqCst),
+            duration,
+        });
+    }
+
+    Ok(runs)
+}
+
+/// Benchmarks solving `game`, printing every run and their average.
+pub async fn bench<
+    T: Game<Player = impl TwoPlayer + Sync + Send + 'static> + Eq + Hash + Sync + Send + 'static,
+>(
+    game: &T,
+    iterations: usize,
+    seed: Option<u64>,
+) -> Result<(), GameSolveError<T>>
+where
+    T::Move: Sync + Send,
+    T::MoveError: Sync + Send,
+    T::Key: Sync + Send,
+{
+    let runs = bench_runs(game, iterations, seed).await?;
+
+    for (index, run) in runs.iter().enumerate() {
+        println!(
+            "Run {}: {} states in {:.3?} ({:.0} states/s)",
+            index + 1,
+            run.states_explored,
+            run.duration,
+            run.states_per_second()
+        );
+    }
+
+    if let Some(average) = average(&runs) {
+        println!(
+            "Average: {} states in {:.3?} ({:.0} states/s)",
+            average.states_explored,
+            average.duration,
+            average.states_per_second()
+        );
+    }
+
+    Ok(())
+}
+
+/// The average of `runs`, or `None` if there aren't any.
+fn average(runs: &[BenchRun]) -> Option<BenchRun> {
+    if runs.is_empty() {
+        return None;
+    }
+
+    Some(BenchRun {
+        states_explored: runs.iter().map(|run| run.states_explored).sum::<u64>()
+            / runs.len() as u64,
+        duration: runs.iter().map(|run| run.duration).sum::<Duration>() / runs.len() as u32,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use games::naive_nim::Nim;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn runs_every_iteration() {
+        let game = Nim::new(vec![3, 4, 5]);
+        let runs = bench_runs(&game, 3, None).await.unwrap();
+
+        assert_eq!(runs.len(), 3);
+        assert!(runs.iter().all(|run| run.states_explored > 0));
+
+        let mean = average(&runs).unwrap();
+        assert!(runs.iter().any(|run| run.duration >= mean.duration));
+        assert!(average(&[]).is_none());
+    }
+
+    #[tokio::test]
+    async fn seeded_runs_explore_the_same_states() {
+        let game = Nim::new(vec![3, 4, 5]);
+        let runs = bench_runs(&game, 2, Some(1)).await.unwrap();
+
+        assert_eq!(runs[0].states_explored, runs[1].states_explored);
+    }
+}
@@ -1,21 +1,12 @@
 use std::{
     fmt::Display,
-    future::IntoFuture,
-    sync::{
-        atomic::{AtomicU64, AtomicUsize, Ordering},
-        Arc,
-    },
+    sync::{atomic::Ordering, Arc},
     time::Duration,
 };
 
 use anyhow::Result;
 use core::hash::Hash;
-use game_solver::{
-    game::Game,
-    par_move_scores,
-    player::TwoPlayer,
-    stats::{Stats, TerminalEnds},
-};
+use game_solver::{game::Game, par_move_scores, player::TwoPlayer, stats::Stats};
 use ratatui::{
     buffer::Buffer,
     crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
@@ -23,17 +14,17 @@ use ratatui::{
     style::Stylize,
     symbols::border,
     text::{Line, Text},
-    widgets::{
-        block::{Position, Title},
-        Block, Paragraph, Widget,
-    },
+    widgets::{Block, Paragraph, Widget},
     DefaultTerminal, Frame,
 };
 use std::fmt::Debug;
 use tokio::select;
 use tokio_util::sync::CancellationToken;
 
-use super::report::{scores::show_scores, stats::show_stats};
+use super::report::{
+    scores::show_scores,
+    stats::{show_stats, show_stats_json},
+};
 
 #[derive(Debug)]
 struct App<G: Game> {
@@ -82,15 +73,11 @@ impl<G: Game> App<G> {
 
 impl<G: Game> Widget for &App<G> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let title = Title::from(" game-solver ".bold().green());
-        let instructions = Title::from(Line::from(vec![" Quit ".into(), "<Q> ".blue().bold()]));
+        let title = Line::from(" game-solver ".bold().green());
+        let instructions = Line::from(vec![" Quit ".into(), "<Q> ".blue().bold()]);
         let block = Block::bordered()
-            .title(title.alignment(Alignment::Center))
-            .title(
-                instructions
-                    .alignment(Alignment::Center)
-                    .position(Position::Bottom),
-            )
+            .title_top(title.alignment(Alignment::Center))
+            .title_bottom(instructions.alignment(Alignment::Center))
             .border_set(border::THICK);
 
         let cache_text = Text::from(vec![
@@ -142,15 +129,14 @@ impl<G: Game> Widget for &App<G> {
                     .yellow(),
                 ")".into(),
             ]),
-            // TODO: depth
-            // Line::from(vec![
-            //     "Max Depth: ".into(),
-            //     self.stats
-            //         .max_depth
-            //         .load(Ordering::Relaxed)
-            //         .to_string()
-            //         .yellow(),
-            // ])
+            Line::from(vec![
+                "Max Depth: ".into(),
+                self.stats
+                    .max_depth
+                    .load(Ordering::Relaxed)
+                    .to_string()
+                    .yellow(),
+            ]),
         ]);
 
         Paragraph::new(cache_text)
@@ -171,22 +157,16 @@ pub async fn human_output<
         + 'static,
 >(
     game: T,
+    stats_json: bool,
 ) -> Result<()>
 where
     T::Move: Sync + Send + Display,
     T::MoveError: Sync + Send + Debug,
+    T::Key: Sync + Send,
 {
     let mut terminal = ratatui::init();
 
-    let stats = Arc::new(Stats {
-        states_explored: AtomicU64::new(0),
-        max_depth: AtomicUsize::new(0),
-        cache_hits: AtomicU64::new(0),
-        pruning_cutoffs: AtomicU64::new(0),
-        terminal_ends: TerminalEnds::default(),
-        original_player: game.player(),
-        original_move_count: game.move_count(),
-    });
+    let stats = Arc::new(Stats::new(game.player(), game.move_count()));
 
     let exit = CancellationToken::new();
 
@@ -221,5 +201,10 @@ where
         None => eprintln!("Game solving was cancelled!"),
     }
 
+    // the solving thread has been awaited, so the stats won't change anymore
+    if stats_json {
+        show_stats_json::<T>(&stats);
+    }
+
     Ok(())
 }
@@ -1,23 +1,50 @@
-use std::{fmt::{Debug, Display}, str::FromStr};
+use std::{
+    fmt::{Debug, Display},
+    hash::Hash,
+    str::FromStr,
+};
 
+use game_solver::{
+    game::Game,
+    player::TwoPlayer,
+    solve_best_move_ranked,
+    transposition::{AgingTable, TranspositionTable},
+    MoveRanking,
+};
 use games::util::move_failable;
-use game_solver::{game::Game, player::TwoPlayer};
 
 use games_cli::robot::announce_player;
 use owo_colors::OwoColorize;
 
 use dialoguer::{theme::ColorfulTheme, Input};
 
-pub fn play_interactive<
-    T: Game<Player = impl TwoPlayer + Debug + 'static>
-        + Display
->(
-    mut game: T
-) where <T as Game>::Move: FromStr + Debug, <<T as Game>::Move as FromStr>::Err: Debug {
+/// How many turns the solver remembers the positions it searched for.
+const MAX_TABLE_AGE: usize = 4;
+
+/// Plays `game` from the terminal until it's over.
+///
+/// If `against_solver` is set, the solver replies to every move with its best move.
+pub fn play_interactive<T: Game<Player = impl TwoPlayer + Debug + 'static> + Eq + Hash + Display>(
+    mut game: T,
+    against_solver: bool,
+) where
+    <T as Game>::Move: FromStr + Debug + Display,
+    <<T as Game>::Move as FromStr>::Err: Debug,
+{
+    // positions are shared between turns, so the solver keeps what it learned,
+    // but positions from turns long gone are forgotten
+    let mut transposition_table: AgingTable<T::Key> = AgingTable::new(MAX_TABLE_AGE);
+
     loop {
         print!("{}", game);
         println!();
 
+        let state = game.state();
+        if state.is_terminal() {
+            println!("Game over: {state:?}");
+            break;
+        }
+
         announce_player(&game);
 
         let game_move: String = Input::with_theme(&ColorfulTheme::default())
@@ -29,10 +56,13 @@ pub fn play_interactive<
             Ok(game_move) => {
                 if let Err(err) = move_failable(&mut game, &game_move) {
                     clearscreen::clear().expect("failed to clear screen");
-                    println!("{}", format!("Failed to make move {game_move:?}: {err:?}").red());
+                    println!(
+                        "{}",
+                        format!("Failed to make move {game_move:?}: {err:?}").red()
+                    );
                     continue;
                 }
-            },
+            }
             Err(err) => {
                 clearscreen::clear().expect("failed to clear screen");
                 println!("{}", format!("Invalid move {game_move}: {err:?}").red());
@@ -41,5 +71,36 @@ pub fn play_interactive<
         }
 
         clearscreen::clear().expect("failed to clear screen");
+
+        if !against_solver || game.state().is_terminal() {
+            continue;
+        }
+
+        transposition_table.advance_age();
+        // equally good replies are broken by the moves the game considers promising
+        match solve_best_move_ranked(
+            &game,
+            &mut transposition_table,
+            None,
+            MoveRanking::MoveOrderKey,
+        ) {
+            Ok(Some((solver_move, _))) => {
+                let description = game.describe_move(&solver_move);
+                move_failable(&mut game, &solver_move).expect("the solver's move should be legal");
+                println!(
+                    "Solver: {description} ({solver_move}) - game state: {:?}",
+                    game.state()
+                );
+            }
+            // a playable game without moves is over, which the next loop reports
+            Ok(None) => (),
+            Err(err) => {
+                println!(
+                    "{}",
+                    format!("The solver couldn't find a reply: {err}").red()
+                );
+                break;
+            }
+        }
     }
 }
@@ -1,7 +1,9 @@
+pub mod bench;
 mod human;
 mod report;
 pub mod robot;
 
+use anyhow::{anyhow, Result};
 use game_solver::{
     game::{Game, GameState},
     player::{ImpartialPlayer, TwoPlayer},
@@ -26,16 +28,25 @@ pub async fn play<
 >(
     game: T,
     plain: bool,
-) where
+    json: bool,
+    stats_json: bool,
+) -> Result<()>
+where
     T::Move: Sync + Send + Display,
     T::MoveError: Sync + Send + Debug,
+    T::Key: Sync + Send,
 {
+    // JSON output is only ever scores, so scripts can tell finished games apart by the exit code
+    if json && game.state() != GameState::Playable {
+        return Err(anyhow!("The game is already over: {:?}", game.state()));
+    }
+
     match game.state() {
         GameState::Playable => {
-            if plain {
-                robotic_output(game).await;
+            if plain || json {
+                robotic_output(game, json, stats_json).await?;
             } else {
-                human_output(game).await.unwrap();
+                human_output(game, stats_json).await?;
             }
         }
         GameState::Tie => println!("No moves left! Thus game is already tied!"),
@@ -46,5 +57,8 @@ pub async fn play<
                 println!("Player {player:?} already won this game!");
             }
         }
+        GameState::WinMany(players) => println!("Players {players:?} already won this game!"),
     }
+
+    Ok(())
 }
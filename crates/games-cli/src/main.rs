@@ -1,13 +1,31 @@
 mod interactive;
 
-use anyhow::Result;
+use std::{
+    fmt::{Debug, Display},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use anyhow::{anyhow, Result};
 use clap::Parser;
+use game_solver::game::Game;
 use games::{
-    chomp::Chomp, domineering::Domineering, naive_nim::Nim, order_and_chaos::OrderAndChaos,
-    reversi::Reversi, sprouts::Sprouts, tic_tac_toe::TicTacToe, zener::Zener,
+    chomp::Chomp,
+    connect_four::ConnectFour,
+    domineering::{cram::Cram, DomineeringArgs, DomineeringDyn, DomineeringVariant},
+    kalah::Kalah,
+    naive_nim::Nim,
+    notakto::Notakto,
+    order_and_chaos::{OrderAndChaos, OrderAndChaosArgs},
+    reversi::Reversi,
+    sprouts::Sprouts,
+    tic_tac_toe::TicTacToe,
+    util::transcript::{apply_transcript, read_transcript},
+    wythoff::Wythoff,
+    zener::Zener,
     Games,
 };
-use games_cli::play;
+use games_cli::{bench::bench, play};
 use interactive::play_interactive;
 
 /// `game-solver` is a solving utility that helps analyze various combinatorial games.
@@ -19,11 +37,109 @@ enum Cli {
         command: Games,
         #[arg(short, long)]
         plain: bool,
+        /// Print the move scores as JSON instead, without the board (implies --plain)
+        #[arg(long)]
+        json: bool,
+        /// Print the solver's stats as JSON once solving is done
+        #[arg(long)]
+        stats_json: bool,
+        /// Make the moves in this file (or stdin, if it's -) after the moves given as arguments
+        #[arg(long, value_name = "PATH")]
+        moves_file: Option<PathBuf>,
+    },
+    /// Times solving a game, reporting how many states are explored per second
+    Bench {
+        #[command(subcommand)]
+        command: Games,
+        /// The amount of times to solve the game, to average over
+        #[arg(long, default_value_t = 1)]
+        iterations: usize,
+        /// Hash games deterministically from this seed, so runs are reproducible
+        #[arg(long)]
+        seed: Option<u64>,
+        /// Make the moves in this file (or stdin, if it's -) after the moves given as arguments
+        #[arg(long, value_name = "PATH")]
+        moves_file: Option<PathBuf>,
     },
     Play {
         #[command(subcommand)]
-        command: Games
+        command: Games,
+        /// Let the solver reply to every move with its best move
+        #[arg(long)]
+        against_solver: bool,
+        /// Make the moves in this file (or stdin, if it's -) after the moves given as arguments
+        #[arg(long, value_name = "PATH")]
+        moves_file: Option<PathBuf>,
+    },
+}
+
+/// Replays the moves in `moves_file` on `game`, if there is one.
+fn load<T: Game>(mut game: T, moves_file: Option<&Path>) -> Result<T>
+where
+    T::Move: FromStr,
+    <T::Move as FromStr>::Err: Display,
+    T::MoveError: Display,
+    T::Player: Debug,
+{
+    if let Some(path) = moves_file {
+        apply_transcript(&mut game, &read_transcript(path)?)?;
     }
+
+    Ok(game)
+}
+
+/// Runs `$body` with `$game` bound to the Domineering variant built from `$args`.
+macro_rules! with_domineering {
+    ($args:expr, |$game:ident| $body:expr) => {{
+        let args: DomineeringArgs = $args;
+        match args.variant() {
+            DomineeringVariant::Cram => {
+                let $game: Cram = args.try_into()?;
+                $body
+            }
+            DomineeringVariant::Normal | DomineeringVariant::Misere => {
+                let $game: DomineeringDyn = args.try_into()?;
+                $body
+            }
+        }
+    }};
+}
+
+/// Runs `$body` with `$game` bound to an Order and Chaos game built from `$args`.
+///
+/// The board size of Order and Chaos is a const generic, so only the sizes listed here are supported.
+macro_rules! with_order_and_chaos {
+    ($args:expr, |$game:ident| $body:expr) => {{
+        let args: OrderAndChaosArgs = $args;
+        match args.board_size() {
+            (3, 3, 3) => {
+                let $game: OrderAndChaos<3, 3, 3, 3> = args.try_into()?;
+                $body
+            }
+            (4, 4, 4) => {
+                let $game: OrderAndChaos<4, 4, 4, 4> = args.try_into()?;
+                $body
+            }
+            (5, 5, 4) => {
+                let $game: OrderAndChaos<5, 5, 4, 5> = args.try_into()?;
+                $body
+            }
+            (5, 5, 5) => {
+                let $game: OrderAndChaos<5, 5, 5, 5> = args.try_into()?;
+                $body
+            }
+            (6, 6, 5) => {
+                let $game: OrderAndChaos<6, 6, 5, 6> = args.try_into()?;
+                $body
+            }
+            (width, height, win_length) => {
+                return Err(anyhow!(
+                    "a {width}x{height} Order and Chaos board with a win length of {win_length} isn't supported - \
+                     try 3x3 (3), 4x4 (4), 5x5 (4 or 5), or 6x6 (5)."
+                ))
+            }
+        }
+    }};
 }
 
 #[tokio::main]
@@ -31,26 +147,271 @@ async fn main() -> Result<()> {
     let cli = Cli::parse();
 
     match cli {
-        Cli::Solve { command, plain } => match command {
-            Games::Reversi(args) => play::<Reversi>(args.try_into().unwrap(), plain).await,
-            Games::TicTacToe(args) => play::<TicTacToe>(args.try_into().unwrap(), plain).await,
-            Games::OrderAndChaos(args) => play::<OrderAndChaos<6, 6, 5, 6>>(args.try_into().unwrap(), plain).await,
-            Games::NaiveNim(args) => play::<Nim>(args.try_into().unwrap(), plain).await,
-            Games::Domineering(args) => play::<Domineering<5, 5>>(args.try_into().unwrap(), plain).await,
-            Games::Chomp(args) => play::<Chomp>(args.try_into().unwrap(), plain).await,
-            Games::Sprouts(args) => play::<Sprouts>(args.try_into().unwrap(), plain).await,
-            Games::Zener(args) => play::<Zener>(args.try_into().unwrap(), plain).await,
+        Cli::Solve {
+            command,
+            plain,
+            json,
+            stats_json,
+            moves_file,
+        } => match command {
+            Games::Reversi(args) => {
+                play::<Reversi>(
+                    load(args.try_into()?, moves_file.as_deref())?,
+                    plain,
+                    json,
+                    stats_json,
+                )
+                .await?
+            }
+            Games::TicTacToe(args) => {
+                play::<TicTacToe>(
+                    load(args.try_into()?, moves_file.as_deref())?,
+                    plain,
+                    json,
+                    stats_json,
+                )
+                .await?
+            }
+            Games::OrderAndChaos(args) => with_order_and_chaos!(args, |game| play(
+                load(game, moves_file.as_deref())?,
+                plain,
+                json,
+                stats_json
+            )
+            .await?),
+            Games::NaiveNim(args) => {
+                play::<Nim>(
+                    load(args.try_into()?, moves_file.as_deref())?,
+                    plain,
+                    json,
+                    stats_json,
+                )
+                .await?
+            }
+            Games::Domineering(args) => with_domineering!(args, |game| play(
+                load(game, moves_file.as_deref())?,
+                plain,
+                json,
+                stats_json
+            )
+            .await?),
+            Games::Chomp(args) => {
+                play::<Chomp>(
+                    load(args.try_into()?, moves_file.as_deref())?,
+                    plain,
+                    json,
+                    stats_json,
+                )
+                .await?
+            }
+            Games::Sprouts(args) => {
+                play::<Sprouts>(
+                    load(args.try_into()?, moves_file.as_deref())?,
+                    plain,
+                    json,
+                    stats_json,
+                )
+                .await?
+            }
+            Games::Zener(args) => {
+                play::<Zener>(
+                    load(args.try_into()?, moves_file.as_deref())?,
+                    plain,
+                    json,
+                    stats_json,
+                )
+                .await?
+            }
+            Games::ConnectFour(args) => {
+                play::<ConnectFour>(
+                    load(args.try_into()?, moves_file.as_deref())?,
+                    plain,
+                    json,
+                    stats_json,
+                )
+                .await?
+            }
+            Games::Notakto(args) => {
+                play::<Notakto>(
+                    load(args.try_into()?, moves_file.as_deref())?,
+                    plain,
+                    json,
+                    stats_json,
+                )
+                .await?
+            }
+            Games::Kalah(args) => {
+                play::<Kalah>(
+                    load(args.try_into()?, moves_file.as_deref())?,
+                    plain,
+                    json,
+                    stats_json,
+                )
+                .await?
+            }
+            Games::Wythoff(args) => {
+                play::<Wythoff>(
+                    load(args.try_into()?, moves_file.as_deref())?,
+                    plain,
+                    json,
+                    stats_json,
+                )
+                .await?
+            }
+        },
+        Cli::Bench {
+            command,
+            iterations,
+            seed,
+            moves_file,
+        } => match command {
+            Games::Reversi(args) => {
+                bench::<Reversi>(
+                    &load(args.try_into()?, moves_file.as_deref())?,
+                    iterations,
+                    seed,
+                )
+                .await?
+            }
+            Games::TicTacToe(args) => {
+                bench::<TicTacToe>(
+                    &load(args.try_into()?, moves_file.as_deref())?,
+                    iterations,
+                    seed,
+                )
+                .await?
+            }
+            Games::OrderAndChaos(args) => with_order_and_chaos!(args, |game| bench(
+                &load(game, moves_file.as_deref())?,
+                iterations,
+                seed
+            )
+            .await?),
+            Games::NaiveNim(args) => {
+                bench::<Nim>(
+                    &load(args.try_into()?, moves_file.as_deref())?,
+                    iterations,
+                    seed,
+                )
+                .await?
+            }
+            Games::Domineering(args) => with_domineering!(args, |game| bench(
+                &load(game, moves_file.as_deref())?,
+                iterations,
+                seed
+            )
+            .await?),
+            Games::Chomp(args) => {
+                bench::<Chomp>(
+                    &load(args.try_into()?, moves_file.as_deref())?,
+                    iterations,
+                    seed,
+                )
+                .await?
+            }
+            Games::Sprouts(args) => {
+                bench::<Sprouts>(
+                    &load(args.try_into()?, moves_file.as_deref())?,
+                    iterations,
+                    seed,
+                )
+                .await?
+            }
+            Games::Zener(args) => {
+                bench::<Zener>(
+                    &load(args.try_into()?, moves_file.as_deref())?,
+                    iterations,
+                    seed,
+                )
+                .await?
+            }
+            Games::ConnectFour(args) => {
+                bench::<ConnectFour>(
+                    &load(args.try_into()?, moves_file.as_deref())?,
+                    iterations,
+                    seed,
+                )
+                .await?
+            }
+            Games::Notakto(args) => {
+                bench::<Notakto>(
+                    &load(args.try_into()?, moves_file.as_deref())?,
+                    iterations,
+                    seed,
+                )
+                .await?
+            }
+            Games::Kalah(args) => {
+                bench::<Kalah>(
+                    &load(args.try_into()?, moves_file.as_deref())?,
+                    iterations,
+                    seed,
+                )
+                .await?
+            }
+            Games::Wythoff(args) => {
+                bench::<Wythoff>(
+                    &load(args.try_into()?, moves_file.as_deref())?,
+                    iterations,
+                    seed,
+                )
+                .await?
+            }
+        },
+        Cli::Play {
+            command,
+            against_solver,
+            moves_file,
+        } => match command {
+            Games::Reversi(args) => play_interactive::<Reversi>(
+                load(args.try_into()?, moves_file.as_deref())?,
+                against_solver,
+            ),
+            Games::TicTacToe(args) => play_interactive::<TicTacToe>(
+                load(args.try_into()?, moves_file.as_deref())?,
+                against_solver,
+            ),
+            Games::OrderAndChaos(args) => with_order_and_chaos!(args, |game| play_interactive(
+                load(game, moves_file.as_deref())?,
+                against_solver
+            )),
+            Games::NaiveNim(args) => play_interactive::<Nim>(
+                load(args.try_into()?, moves_file.as_deref())?,
+                against_solver,
+            ),
+            Games::Domineering(args) => with_domineering!(args, |game| play_interactive(
+                load(game, moves_file.as_deref())?,
+                against_solver
+            )),
+            Games::Chomp(args) => play_interactive::<Chomp>(
+                load(args.try_into()?, moves_file.as_deref())?,
+                against_solver,
+            ),
+            Games::Sprouts(args) => play_interactive::<Sprouts>(
+                load(args.try_into()?, moves_file.as_deref())?,
+                against_solver,
+            ),
+            Games::Zener(args) => play_interactive::<Zener>(
+                load(args.try_into()?, moves_file.as_deref())?,
+                against_solver,
+            ),
+            Games::ConnectFour(args) => play_interactive::<ConnectFour>(
+                load(args.try_into()?, moves_file.as_deref())?,
+                against_solver,
+            ),
+            Games::Notakto(args) => play_interactive::<Notakto>(
+                load(args.try_into()?, moves_file.as_deref())?,
+                against_solver,
+            ),
+            Games::Kalah(args) => play_interactive::<Kalah>(
+                load(args.try_into()?, moves_file.as_deref())?,
+                against_solver,
+            ),
+            Games::Wythoff(args) => play_interactive::<Wythoff>(
+                load(args.try_into()?, moves_file.as_deref())?,
+                against_solver,
+            ),
         },
-        Cli::Play { command } => match command {
-            Games::Reversi(args) => play_interactive::<Reversi>(args.try_into().unwrap()),
-            Games::TicTacToe(args) => play_interactive::<TicTacToe>(args.try_into().unwrap()),
-            Games::OrderAndChaos(args) => play_interactive::<OrderAndChaos<6, 6, 5, 6>>(args.try_into().unwrap()),
-            Games::NaiveNim(args) => play_interactive::<Nim>(args.try_into().unwrap()),
-            Games::Domineering(args) => play_interactive::<Domineering<5, 5>>(args.try_into().unwrap()),
-            Games::Chomp(args) => play_interactive::<Chomp>(args.try_into().unwrap()),
-            Games::Sprouts(args) => play_interactive::<Sprouts>(args.try_into().unwrap()),
-            Games::Zener(args) => play_interactive::<Zener>(args.try_into().unwrap()),
-        }
     };
 
     Ok(())
@@ -1,24 +1,58 @@
 use std::fmt::{Debug, Display};
 
+use anyhow::{anyhow, Result};
 use game_solver::{
     game::{score_to_outcome, Game, GameScoreOutcome},
-    CollectedMoves, GameSolveError,
+    player::TwoPlayer,
+    verdict, CollectedMoves, GameSolveError, MoveRanking,
 };
-use games::util::move_score::normalize_move_scores;
+use serde_json::{json, Value};
 
-pub fn show_scores<T: Game + Debug>(game: &T, move_scores: CollectedMoves<T>)
-where
+/// Describes why solving failed, for printing to the user.
+fn describe_error<T: Game>(err: GameSolveError<T>) -> String {
+    match err {
+        GameSolveError::MoveError(err) => format!("Error making move: {:?}", err),
+        GameSolveError::Cancelled => "Game solving was cancelled!".to_string(),
+        GameSolveError::GameOver(state) => format!("The game is already over: {:?}", state),
+        GameSolveError::Unbounded => {
+            "The game may never end - try solving it with a depth limit.".to_string()
+        }
+        GameSolveError::NotNormalPlay => "The game isn't played under normal play.".to_string(),
+    }
+}
+
+/// Sorts the move scores from best to worst.
+///
+/// Moves are ranked like the interactive mode picks them, so the best move comes first.
+fn sorted_scores<T: Game>(
+    game: &T,
+    move_scores: Result<CollectedMoves<T>, GameSolveError<T>>,
+) -> Result<Vec<(T::Move, isize)>, GameSolveError<T>> {
+    let mut move_scores = move_scores?.into_iter().collect::<Result<Vec<_>, _>>()?;
+    MoveRanking::MoveOrderKey.sort(game, &mut move_scores);
+
+    Ok(move_scores)
+}
+
+pub fn show_scores<T: Game<Player = impl TwoPlayer> + Debug>(
+    game: &T,
+    move_scores: Result<CollectedMoves<T>, GameSolveError<T>>,
+) where
     T::Move: Display,
 {
-    let move_scores = normalize_move_scores::<T>(move_scores).unwrap_or_else(|err| {
-        match err {
-            GameSolveError::MoveError(err) => {
-                eprintln!("Error making move: {:?}", err);
-            },
-            GameSolveError::Cancelled => {
-                eprintln!("Game solving was cancelled!");
-            },
+    if let Ok(collected) = &move_scores {
+        if collected.is_empty() {
+            println!(
+                "\n\nThere are no moves to play: {:?}",
+                verdict(collected, game)
+            );
+            return;
         }
+    }
+
+    // errors are reported to stderr, leaving no moves to show
+    let move_scores = sorted_scores(game, move_scores).unwrap_or_else(|err| {
+        eprintln!("{}", describe_error(err));
         vec![]
     });
 
@@ -26,18 +60,28 @@ where
     for (game_move, score) in move_scores {
         if current_move_score != Some(score) {
             match score_to_outcome(game, score) {
-                GameScoreOutcome::Win(moves) => println!(
+                GameScoreOutcome::Win {
+                    distance: Some(moves),
+                } => println!(
                     "\n\nWin in {} move{} (score {}):",
                     moves,
                     if moves == 1 { "" } else { "s" },
                     score
                 ),
-                GameScoreOutcome::Loss(moves) => println!(
+                GameScoreOutcome::Win { distance: None } => {
+                    println!("\n\nWin (distance unknown) (score {}):", score)
+                }
+                GameScoreOutcome::Loss {
+                    distance: Some(moves),
+                } => println!(
                     "\n\nLose in {} move{} (score {}):",
                     moves,
                     if moves == 1 { "" } else { "s" },
                     score
                 ),
+                GameScoreOutcome::Loss { distance: None } => {
+                    println!("\n\nLose (distance unknown) (score {}):", score)
+                }
                 GameScoreOutcome::Tie => println!("\n\nTie with the following moves:"),
             }
             current_move_score = Some(score);
@@ -46,3 +90,88 @@ where
     }
     println!();
 }
+
+/// Converts move scores, sorted from best to worst, into JSON:
+/// every move with its score and outcome, and the best of them (or `null` without any moves).
+fn scores_json<T: Game>(game: &T, move_scores: &[(T::Move, isize)]) -> Value
+where
+    T::Move: Display,
+{
+    let moves = move_scores
+        .iter()
+        .map(|(game_move, score)| {
+            let (outcome, distance) = match score_to_outcome(game, *score) {
+                GameScoreOutcome::Win { distance } => ("win", distance),
+                GameScoreOutcome::Loss { distance } => ("loss", distance),
+                GameScoreOutcome::Tie => ("tie", None),
+            };
+
+            json!({
+                "move": game_move.to_string(),
+                "score": score,
+                "outcome": outcome,
+                "distance": distance,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "best": moves.first(),
+        "moves": moves,
+    })
+}
+
+/// Prints the move scores as JSON, for scripts that use the solver.
+///
+/// # Errors
+///
+/// If solving failed, so scripts can tell it apart from a game without any moves.
+pub fn show_scores_json<T: Game>(
+    game: &T,
+    move_scores: Result<CollectedMoves<T>, GameSolveError<T>>,
+) -> Result<()>
+where
+    T::Move: Display,
+{
+    let move_scores =
+        sorted_scores(game, move_scores).map_err(|err| anyhow!(describe_error(err)))?;
+    println!("{}", scores_json(game, &move_scores));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use games::naive_nim::Nim;
+
+    use super::*;
+
+    #[test]
+    fn json_scores() {
+        let game = Nim::new(vec![1, 2]);
+        let mut moves = game.possible_moves();
+        let json = scores_json(
+            &game,
+            &[(moves.next().unwrap(), 2), (moves.next().unwrap(), -1)],
+        );
+
+        assert_eq!(json["moves"].as_array().unwrap().len(), 2);
+        assert_eq!(json["best"], json["moves"][0]);
+        assert_eq!(json["moves"][0]["outcome"], "win");
+        assert_eq!(json["moves"][0]["distance"], 1);
+        assert_eq!(json["moves"][1]["outcome"], "loss");
+        assert_eq!(json["moves"][1]["score"], -1);
+
+        assert_eq!(
+            scores_json(&game, &[]),
+            json!({ "best": null, "moves": [] })
+        );
+    }
+
+    #[test]
+    fn json_scores_fail_with_the_solver() {
+        let game = Nim::new(vec![1, 2]);
+        assert!(show_scores_json(&game, Err(GameSolveError::Cancelled)).is_err());
+        assert!(show_scores_json(&game, Ok(vec![Err(GameSolveError::Unbounded)])).is_err());
+    }
+}
@@ -24,6 +24,8 @@ pub fn show_stats<T: Game>(
         "Pruning cutoffs: {}",
         stats.pruning_cutoffs.load(Ordering::SeqCst)
     );
+    println!("Branch factor:   {:.3}", stats.effective_branching_factor());
+    println!("Cache hit rate:  {:.2}%", stats.cache_hit_rate() * 100.0);
     println!("End nodes:");
     println!(
         "\tWinning: {}",
@@ -39,3 +41,11 @@ pub fn show_stats<T: Game>(
     );
     println!();
 }
+
+/// Prints the stats as JSON, for tools that track them across runs.
+pub fn show_stats_json<T: Game>(stats: &Stats<T::Player>) {
+    println!(
+        "{}",
+        serde_json::to_string(stats).expect("stats should always serialize")
+    );
+}
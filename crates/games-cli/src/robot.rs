@@ -1,16 +1,22 @@
+use anyhow::Result;
 use game_solver::{
     game::Game,
     par_move_scores,
     player::{ImpartialPlayer, TwoPlayer},
+    stats::Stats,
 };
-use tokio_util::sync::CancellationToken;
 use std::{
     any::TypeId,
     fmt::{Debug, Display},
     hash::Hash,
+    sync::Arc,
 };
+use tokio_util::sync::CancellationToken;
 
-use crate::report::scores::show_scores;
+use crate::report::{
+    scores::{show_scores, show_scores_json},
+    stats::show_stats_json,
+};
 
 pub fn announce_player<T: Game<Player = impl TwoPlayer + Debug + 'static>>(game: &T) {
     if TypeId::of::<T::Player>() != TypeId::of::<ImpartialPlayer>() {
@@ -32,14 +38,21 @@ pub async fn robotic_output<
         + 'static,
 >(
     game: T,
-) where
+    json: bool,
+    stats_json: bool,
+) -> Result<()>
+where
     T::Move: Sync + Send + Display,
     T::MoveError: Sync + Send + Debug,
+    T::Key: Sync + Send,
 {
-    print!("{}", game);
-    println!();
+    // JSON output shouldn't be mixed with anything else
+    if !json {
+        print!("{}", game);
+        println!();
 
-    announce_player(&game);
+        announce_player(&game);
+    }
 
     let cancellation_token = CancellationToken::new();
 
@@ -48,13 +61,24 @@ pub async fn robotic_output<
     let ctrl_c = tokio::signal::ctrl_c();
     let handle = tokio::spawn(async move {
         ctrl_c.await.expect("Failed to listen for Ctrl+C");
-        println!("Cancelling...");
+        eprintln!("Cancelling...");
         exit.cancel();
     });
 
-    let move_scores = par_move_scores(&game, None, Some(cancellation_token)).await;
-
-    show_scores(&game, move_scores);
+    let stats = Arc::new(Stats::new(game.player(), game.move_count()));
+    let move_scores = par_move_scores(&game, Some(stats.clone()), Some(cancellation_token)).await;
 
     handle.abort();
+
+    if json {
+        show_scores_json(&game, move_scores)?;
+    } else {
+        show_scores(&game, move_scores);
+    }
+
+    if stats_json {
+        show_stats_json::<T>(&stats);
+    }
+
+    Ok(())
 }
@@ -1,5 +1,7 @@
 use games::{naive_nim, util::gui::egui_display::EguiDisplay, Games, DEFAULT_GAMES};
 
+use crate::board::TicTacToeBoard;
+
 /// We derive Deserialize/Serialize so we can persist app state on shutdown.
 #[derive(serde::Deserialize, serde::Serialize)]
 #[serde(default)] // if we add new fields, give them default values when deserializing old state
@@ -7,6 +9,9 @@ use games::{naive_nim, util::gui::egui_display::EguiDisplay, Games, DEFAULT_GAME
 pub struct TemplateApp {
     /// The currently selected game
     selected_game: Option<Games>,
+    /// The board of the selected game, if it can be played
+    #[serde(skip)]
+    board: Option<TicTacToeBoard>,
 }
 
 impl TemplateApp {
@@ -52,9 +57,14 @@ impl eframe::App for TemplateApp {
             egui::ComboBox::from_label("Select a game")
                 .selected_text(self.selected_game.as_ref().map(|game| game.name()).unwrap_or("No game".to_string()))
                 .show_ui(ui, |ui| {
-                    ui.selectable_value(&mut self.selected_game, None, "No game");
+                    let mut changed = ui.selectable_value(&mut self.selected_game, None, "No game").changed();
                     for game in DEFAULT_GAMES.iter() {
-                        ui.selectable_value(&mut self.selected_game, Some(game.clone()), game.name());
+                        changed |= ui.selectable_value(&mut self.selected_game, Some(game.clone()), game.name()).changed();
+                    }
+
+                    // a different game needs a new board
+                    if changed {
+                        self.board = None;
                     }
                 });
 
@@ -66,9 +76,17 @@ impl eframe::App for TemplateApp {
                     game.description_egui(ui)
                 });
 
-                let game = naive_nim::Nim::new(vec![5, 3, 1]);
+                if let Games::TicTacToe(args) = game {
+                    self.board
+                        .get_or_insert_with(|| {
+                            TicTacToeBoard::new(args.clone().try_into().expect("the default board is valid"))
+                        })
+                        .show(ui);
+                } else {
+                    let game = naive_nim::Nim::new(vec![5, 3, 1]);
 
-                game.display(ui);
+                    game.display(ui);
+                }
             } else {
                 ui.label("To get started, select a game from the above dropdown.");
             }
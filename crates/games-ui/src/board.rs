@@ -0,0 +1,241 @@
+use std::collections::HashMap;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+
+#[cfg(not(target_arch = "wasm32"))]
+use game_solver::par_move_scores_streaming;
+use game_solver::{
+    game::{Game, GameState},
+    player::PartizanPlayer,
+};
+#[cfg(target_arch = "wasm32")]
+use game_solver::{solve, transposition::Score, SolveMode};
+use games::tic_tac_toe::{TicTacToe, TicTacToeMove};
+#[cfg(not(target_arch = "wasm32"))]
+use tokio_util::sync::CancellationToken;
+
+use crate::heatmap::heatmap;
+
+/// A Tic Tac Toe game that can be played by clicking on its squares,
+/// with the scores of every move shown once it's solved.
+pub struct TicTacToeBoard {
+    start: TicTacToe,
+    game: TicTacToe,
+    /// The scores of the moves in the current position, once it's been solved
+    scores: Vec<(TicTacToeMove, isize)>,
+    /// The scores being computed in the background, if the game is being solved
+    solving: Option<Solving>,
+    error: Option<String>,
+    /// Whether to color the scored moves by how good they are
+    show_heatmap: bool,
+}
+
+impl TicTacToeBoard {
+    pub fn new(game: TicTacToe) -> Self {
+        Self {
+            start: game.clone(),
+            game,
+            scores: vec![],
+            solving: None,
+            error: None,
+            show_heatmap: true,
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        self.receive_scores(ui.ctx());
+
+        let state = self.game.state();
+        let playable = state.is_playable();
+
+        let colors = if self.show_heatmap {
+            heatmap(&self.game, &self.scores)
+        } else {
+            HashMap::new()
+        };
+
+        if let Some(m) = self.game.board_ui(ui, playable, &self.scores, &colors) {
+            if self.game.make_move(&m).is_ok() {
+                // the scores (and any solve in progress) are for the previous position
+                self.scores.clear();
+                self.solving = None;
+                self.error = None;
+            }
+        }
+
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(
+                    playable && self.solving.is_none(),
+                    egui::Button::new("Solve"),
+                )
+                .clicked()
+            {
+                self.solving = Some(Solving::start(self.game.clone()));
+            }
+
+            if ui.button("Reset").clicked() {
+                let show_heatmap = self.show_heatmap;
+                *self = Self::new(self.start.clone());
+                self.show_heatmap = show_heatmap;
+            }
+
+            ui.checkbox(&mut self.show_heatmap, "Heatmap");
+
+            if self.solving.is_some() {
+                ui.spinner();
+            }
+        });
+
+        match state {
+            GameState::Playable => ui.label(match self.game.player() {
+                PartizanPlayer::Left => "X to move",
+                PartizanPlayer::Right => "O to move",
+            }),
+            GameState::Win(PartizanPlayer::Left) => ui.label("X won!"),
+            GameState::Win(PartizanPlayer::Right) => ui.label("O won!"),
+            GameState::Tie => ui.label("It's a tie!"),
+            state => ui.label(format!("{state:?}")),
+        };
+
+        if let Some(error) = &self.error {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+        }
+    }
+
+    /// Takes the scores from the background solve, if it's done.
+    fn receive_scores(&mut self, ctx: &egui::Context) {
+        let Some(solving) = &mut self.solving else {
+            return;
+        };
+
+        match solving.poll() {
+            Some(Ok(scores)) => {
+                self.scores = scores;
+                self.solving = None;
+            }
+            Some(Err(err)) => {
+                self.error = Some(err);
+                self.solving = None;
+            }
+            // keep repainting, so the scores show up as soon as they're ready
+            None => ctx.request_repaint(),
+        }
+    }
+}
+
+/// The scores of every move of a position, being computed in the background.
+///
+/// Dropping it stops the solve, even if it's halfway through.
+#[cfg(not(target_arch = "wasm32"))]
+struct Solving {
+    receiver: Receiver<Result<Vec<(TicTacToeMove, isize)>, String>>,
+    cancellation_token: CancellationToken,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Solving {
+    /// Starts scoring every move of `game` on another thread.
+    fn start(game: TicTacToe) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let cancellation_token = CancellationToken::new();
+        let token = cancellation_token.clone();
+
+        std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_multi_thread()
+                .build()
+                .expect("failed to start the solver");
+            let _guard = runtime.enter();
+
+            // unlike `par_move_scores`, cancelling the token also stops
+            // the searches of the moves that are still being scored
+            let scores = par_move_scores_streaming(&game, None, Some(token))
+                .and_then(|moves| moves.into_iter().collect())
+                .map_err(|err| format!("Couldn't solve the game: {err}"));
+
+            // nobody is waiting for the scores anymore if a move was made in the meantime
+            let _ = sender.send(scores);
+        });
+
+        Self {
+            receiver,
+            cancellation_token,
+        }
+    }
+
+    /// The scores of every move, or why they couldn't be found, once the solve is done.
+    fn poll(&mut self) -> Option<Result<Vec<(TicTacToeMove, isize)>, String>> {
+        match self.receiver.try_recv() {
+            Ok(scores) => Some(scores),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => {
+                Some(Err("Solving stopped unexpectedly.".to_string()))
+            }
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Drop for Solving {
+    fn drop(&mut self) {
+        self.cancellation_token.cancel();
+    }
+}
+
+/// The scores of every move of a position, being computed in the background.
+///
+/// There are no threads to solve on in the browser, so every call to [`Solving::poll`]
+/// scores a single move, giving the browser a chance to draw between moves.
+/// Dropping it stops the solve before the next move.
+#[cfg(target_arch = "wasm32")]
+struct Solving {
+    game: TicTacToe,
+    moves: std::vec::IntoIter<TicTacToeMove>,
+    transposition_table: HashMap<<TicTacToe as Game>::Key, Score>,
+    scores: Vec<(TicTacToeMove, isize)>,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Solving {
+    /// Starts scoring every move of `game`, one move per [`Solving::poll`].
+    fn start(game: TicTacToe) -> Self {
+        Self {
+            moves: game.possible_moves().collect::<Vec<_>>().into_iter(),
+            game,
+            transposition_table: HashMap::new(),
+            scores: vec![],
+        }
+    }
+
+    /// Scores the next move, returning the scores of every move, or why they couldn't be found,
+    /// once the solve is done.
+    fn poll(&mut self) -> Option<Result<Vec<(TicTacToeMove, isize)>, String>> {
+        let Some(m) = self.moves.next() else {
+            return Some(Ok(std::mem::take(&mut self.scores)));
+        };
+
+        let mut board = self.game.clone();
+        if let Err(err) = board.make_move(&m) {
+            return Some(Err(format!("Couldn't make the move {m}: {err}")));
+        }
+
+        match solve(
+            &board,
+            &mut self.transposition_table,
+            SolveMode::Strong,
+            None,
+        ) {
+            Ok(score) => {
+                // the score is from the perspective of whoever moves next
+                let score = if self.game.same_player_after(&m) {
+                    score
+                } else {
+                    -score
+                };
+                self.scores.push((m, score));
+                None
+            }
+            Err(err) => Some(Err(format!("Couldn't solve the game: {err}"))),
+        }
+    }
+}
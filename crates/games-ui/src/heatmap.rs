@@ -0,0 +1,53 @@
+use std::collections::HashMap;
+
+use egui::Color32;
+use game_solver::game::{score_to_outcome, GameScoreOutcome};
+use games::util::gui::move_cell::MoveToCell;
+
+/// Colors the cell of every scored move by how good it is for the player to move:
+/// green for wins, red for losses, and gray for ties.
+///
+/// The sooner a move wins or loses, the stronger its color is.
+pub fn heatmap<T: MoveToCell>(
+    game: &T,
+    scores: &[(T::Move, isize)],
+) -> HashMap<(usize, usize), Color32> {
+    let outcomes = scores
+        .iter()
+        .filter_map(|(m, score)| Some((game.move_to_cell(m)?, score_to_outcome(game, *score))))
+        .collect::<Vec<_>>();
+
+    // distances are shaded relative to the slowest outcome on the board
+    let longest = outcomes
+        .iter()
+        .filter_map(|(_, outcome)| match outcome {
+            GameScoreOutcome::Win { distance } | GameScoreOutcome::Loss { distance } => *distance,
+            GameScoreOutcome::Tie => None,
+        })
+        .max()
+        .unwrap_or(0);
+
+    outcomes
+        .into_iter()
+        .map(|(cell, outcome)| (cell, outcome_color(outcome, longest)))
+        .collect()
+}
+
+fn outcome_color(outcome: GameScoreOutcome, longest: usize) -> Color32 {
+    // even the slowest outcome should be visible, and unknown distances are shown as the slowest
+    let strength = |distance: Option<usize>| {
+        let distance = distance.unwrap_or(longest).min(longest);
+        let alpha = 1.0 - 0.75 * distance as f32 / longest.max(1) as f32;
+        (alpha * 255.0) as u8
+    };
+
+    match outcome {
+        GameScoreOutcome::Win { distance } => {
+            Color32::from_rgba_unmultiplied(0, 170, 0, strength(distance))
+        }
+        GameScoreOutcome::Loss { distance } => {
+            Color32::from_rgba_unmultiplied(200, 0, 0, strength(distance))
+        }
+        GameScoreOutcome::Tie => Color32::from_rgba_unmultiplied(128, 128, 128, 96),
+    }
+}
@@ -1,4 +1,6 @@
 #![warn(clippy::all, rust_2018_idioms)]
 
 mod app;
+mod board;
+mod heatmap;
 pub use app::TemplateApp;
@@ -2,7 +2,7 @@
 
 #[cfg(feature = "egui")]
 pub mod gui;
-use anyhow::Error;
+use anyhow::{anyhow, Error};
 use array2d::Array2D;
 use clap::Args;
 use game_solver::{
@@ -13,26 +13,33 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use std::{
+    collections::HashMap,
     fmt::{Debug, Display, Formatter},
     hash::Hash,
 };
 
-use crate::util::{move_failable, move_natural::NaturalMove};
+use crate::util::{
+    bitboard::{BitBoard, BoolGrid},
+    move_failable,
+    move_natural::NaturalMove,
+};
 
 #[derive(Clone, Hash, Eq, PartialEq)]
 pub struct Chomp {
     width: usize,
     height: usize,
-    // TODO: bit array 2d
     /// True represents a square that has not been eaten
-    board: Array2D<bool>,
+    board: BitBoard,
     move_count: usize,
 }
 
 impl Chomp {
+    /// # Panics
+    ///
+    /// If the board has no squares, or more than [`BitBoard::MAX_CELLS`] squares.
     pub fn new(width: usize, height: usize) -> Self {
-        let mut board = Array2D::filled_with(true, width, height);
-        board.set(0, height - 1, false).unwrap();
+        let mut board = BitBoard::filled_with(true, width, height);
+        board.set_cell(0, height - 1, false).unwrap();
 
         Self {
             width,
@@ -41,6 +48,116 @@ impl Chomp {
             move_count: 0,
         }
     }
+
+    /// Create a game of Chomp that starts from `board`, where true represents a square that has not been eaten,
+    /// indexed by `(x, y)` from the top left like [`Chomp::new`]'s board.
+    ///
+    /// Since eating a square eats everything to the right and above it, the uneaten squares
+    /// have to form a staircase down to the poisoned square in the bottom left, which can't be eaten.
+    pub fn from_board(width: usize, height: usize, board: Array2D<bool>) -> Result<Self, Error> {
+        if (board.num_rows(), board.num_columns()) != (width, height) {
+            return Err(anyhow!(
+                "expected a {width}x{height} board, but got a {}x{} board.",
+                board.num_rows(),
+                board.num_columns()
+            ));
+        }
+
+        if width * height > BitBoard::MAX_CELLS {
+            return Err(anyhow!(
+                "chomp boards can have at most {} squares.",
+                BitBoard::MAX_CELLS
+            ));
+        }
+
+        if width == 0 || height == 0 || !board[(0, height - 1)] {
+            return Err(anyhow!("the poisoned square can't be eaten."));
+        }
+
+        let mut chomp = Chomp::new(width, height);
+        for (x, y) in (0..width).flat_map(|x| (0..height).map(move |y| (x, y))) {
+            // it's enough to check the squares right next to every eaten square,
+            // since those squares being eaten checks the squares next to them
+            let eaten = !board[(x, y)];
+            if eaten && ((x + 1 < width && board[(x + 1, y)]) || (y > 0 && board[(x, y - 1)])) {
+                return Err(anyhow!(
+                    "square {x}-{y} is eaten, but a square to the right or above it isn't."
+                ));
+            }
+
+            if eaten {
+                chomp.board.set_cell(x, y, false).unwrap();
+            }
+        }
+
+        Ok(chomp)
+    }
+
+    /// The amount of uneaten squares in every row, from top to bottom,
+    /// not counting the poisoned square.
+    ///
+    /// Since eating a square eats everything to the right and above it,
+    /// this uniquely identifies the position.
+    pub fn row_lengths(&self) -> Vec<usize> {
+        (0..self.height)
+            .map(|j| {
+                (0..self.width)
+                    .filter(|&i| self.board.get_cell(i, j).unwrap())
+                    .count()
+            })
+            .collect()
+    }
+}
+
+/// The most positions [`value_table`] will compute.
+pub const MAX_TABLE_POSITIONS: usize = 1 << 20;
+
+/// Computes whether every position reachable from a `width` x `height` game of Chomp
+/// is won by the player to move (an N-position) or by the other player (a P-position),
+/// keyed by [`Chomp::row_lengths`].
+///
+/// The starting position is always an N-position by strategy stealing,
+/// so this is mainly useful for exploring the positions in between.
+/// Boards with more than [`MAX_TABLE_POSITIONS`] positions
+/// or [`BitBoard::MAX_CELLS`] squares are rejected.
+pub fn value_table(width: usize, height: usize) -> Result<HashMap<Vec<usize>, bool>, Error> {
+    // every position is a staircase through the board, of which there are (width + height) choose height
+    let positions = (1..=height).try_fold(1usize, |positions, i| {
+        positions
+            .checked_mul(width.checked_add(i)?)
+            .map(|positions| positions / i)
+    });
+    if positions.map_or(true, |positions| positions > MAX_TABLE_POSITIONS)
+        || width.saturating_mul(height) > BitBoard::MAX_CELLS
+    {
+        return Err(anyhow!(
+            "a {width}x{height} board is too large to compute every position of."
+        ));
+    }
+
+    let mut table = HashMap::new();
+    fill_value_table(&Chomp::new(width, height), &mut table);
+    Ok(table)
+}
+
+/// Returns whether `game` is an N-position, filling in `table` with it and every position after it.
+fn fill_value_table(game: &Chomp, table: &mut HashMap<Vec<usize>, bool>) -> bool {
+    let row_lengths = game.row_lengths();
+    if let Some(&value) = table.get(&row_lengths) {
+        return value;
+    }
+
+    // a position is an N-position if any move leads to a P-position.
+    // every move has to be visited to fill the table, so this doesn't short-circuit
+    let mut value = false;
+    for m in game.possible_moves() {
+        let mut next = game.clone();
+        next.make_move(&m).unwrap();
+        value |= !fill_value_table(&next, table);
+    }
+
+    table.insert(row_lengths, value);
+    value
 }
 
 /// Analyzes Chomp.
@@ -84,6 +201,7 @@ impl Game for Chomp {
     type Move = ChompMove;
     type Iter<'a> = std::vec::IntoIter<Self::Move>;
     type Player = ImpartialPlayer;
+    type Key = Self;
     type MoveError = ChompMoveError;
 
     fn max_moves(&self) -> Option<usize> {
@@ -95,10 +213,10 @@ impl Game for Chomp {
     }
 
     fn make_move(&mut self, m: &Self::Move) -> Result<(), Self::MoveError> {
-        if *self.board.get(m.0[0], m.0[1]).unwrap() {
+        if self.board.get_cell(m.0[0], m.0[1]).unwrap() {
             for i in m.0[0]..self.width {
                 for j in 0..=m.0[1] {
-                    self.board.set(i, j, false).unwrap();
+                    self.board.set_cell(i, j, false).unwrap();
                 }
             }
             self.move_count += 1;
@@ -112,7 +230,7 @@ impl Game for Chomp {
         let mut moves = Vec::new();
         for i in (0..self.height).rev() {
             for j in 0..self.width {
-                if *self.board.get(j, i).unwrap() {
+                if self.board.get_cell(j, i).unwrap() {
                     moves.push(NaturalMove([j, i]));
                 }
             }
@@ -120,12 +238,30 @@ impl Game for Chomp {
         moves.into_iter()
     }
 
+    /// The game only ends once every square but the poisoned one is eaten, which one move can only do
+    /// if the squares left are all above the poisoned square, or all to the right of it.
+    fn find_immediately_resolvable_game(&self) -> Result<Option<Self>, Self::MoveError> {
+        let bottom = self.height - 1;
+        let right_of_poison = self.width > 1 && self.board.get_cell(1, bottom).unwrap();
+        let above_poison = bottom > 0 && self.board.get_cell(0, bottom - 1).unwrap();
+
+        let m = match (above_poison, right_of_poison) {
+            (true, false) => NaturalMove([0, bottom - 1]),
+            (false, true) => NaturalMove([1, bottom]),
+            _ => return Ok(None),
+        };
+
+        let mut game = self.clone();
+        game.make_move(&m)?;
+        Ok(Some(game))
+    }
+
     fn player(&self) -> Self::Player {
         ImpartialPlayer::Next
     }
 
     fn state(&self) -> GameState<Self::Player> {
-        <Self as Normal>::state(&self)
+        <Self as Normal>::state(self)
     }
 }
 
@@ -133,7 +269,7 @@ impl Display for Chomp {
     fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
         for i in 0..self.height {
             for j in 0..self.width {
-                if *self.board.get(j, i).unwrap() {
+                if self.board.get_cell(j, i).unwrap() {
                     write!(f, "X")?;
                 } else {
                     write!(f, ".")?;
@@ -155,6 +291,25 @@ impl TryFrom<ChompArgs> for Chomp {
     type Error = Error;
 
     fn try_from(args: ChompArgs) -> Result<Self, Self::Error> {
+        if args.width == 0 || args.height == 0 {
+            return Err(anyhow!(
+                "a {}x{} board has no squares - both dimensions must be at least 1.",
+                args.width,
+                args.height
+            ));
+        }
+
+        if args
+            .width
+            .checked_mul(args.height)
+            .map_or(true, |cells| cells > BitBoard::MAX_CELLS)
+        {
+            return Err(anyhow!(
+                "chomp boards can have at most {} squares.",
+                BitBoard::MAX_CELLS
+            ));
+        }
+
         let mut game = Chomp::new(args.width, args.height);
 
         // parse every move in args, e.g. 0-0 1-1 in args
@@ -168,9 +323,12 @@ impl TryFrom<ChompArgs> for Chomp {
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
+    use std::{collections::HashMap, sync::atomic::Ordering};
 
-    use game_solver::{move_scores, GameSolveError};
+    use game_solver::{
+        build_endgame_table, move_scores, solve, stats::Stats, GameSolveError, SolveMode,
+    };
+    use itertools::Itertools;
 
     use super::*;
 
@@ -178,6 +336,7 @@ mod tests {
     fn test_chomp() {
         let game = Chomp::new(6, 4);
         let mut move_scores = move_scores(&game, &mut HashMap::new(), None)
+            .unwrap()
             .collect::<Result<Vec<_>, GameSolveError<Chomp>>>()
             .unwrap();
         move_scores.sort();
@@ -212,4 +371,143 @@ mod tests {
 
         assert_eq!(move_scores, new_scores);
     }
+
+    #[test]
+    fn exact_scores_skip_resolving() {
+        let game = Chomp::new(4, 3);
+        let mut transposition_table = HashMap::new();
+        let score = solve(&game, &mut transposition_table, SolveMode::Strong, None).unwrap();
+
+        let stats = Stats::new(game.player(), game.move_count());
+        assert_eq!(
+            solve(
+                &game,
+                &mut transposition_table,
+                SolveMode::Strong,
+                Some(&stats)
+            )
+            .unwrap(),
+            score
+        );
+
+        // every null window probe returns immediately at the root
+        assert!(stats.states_explored.load(Ordering::Relaxed) < 10);
+    }
+
+    #[test]
+    fn value_table_two_rows() {
+        for width in 1..8 {
+            let table = value_table(width, 2).unwrap();
+
+            // every staircase is reachable
+            assert_eq!(table.len(), (width + 1) * (width + 2) / 2 - 1);
+
+            // the P-positions of two-row chomp are those where the poisoned row
+            // is one square longer than the other
+            for (row_lengths, &value) in &table {
+                assert_eq!(value, row_lengths[0] != row_lengths[1], "{row_lengths:?}");
+            }
+
+            assert!(table[&Chomp::new(width, 2).row_lengths()]);
+        }
+    }
+
+    #[test]
+    fn value_table_matches_search() {
+        let table = value_table(4, 3).unwrap();
+
+        for (x, y) in (0..4).cartesian_product(0..3) {
+            let mut game = Chomp::new(4, 3);
+            if game.make_move(&NaturalMove([x, y])).is_err() {
+                continue;
+            }
+
+            assert_eq!(
+                table[&game.row_lengths()],
+                solve(&game, &mut HashMap::new(), SolveMode::Strong, None).unwrap() > 0
+            );
+        }
+    }
+
+    #[test]
+    fn value_table_is_bounded() {
+        assert!(value_table(100, 100).is_err());
+        assert!(value_table(usize::MAX, 2).is_err());
+    }
+
+    #[test]
+    fn endgame_table_matches_move_scores() {
+        let game = Chomp::new(5, 4);
+        let table = build_endgame_table(&game).unwrap();
+
+        // chomp is always a first player win
+        assert_eq!(table.get(&game), Some(&true));
+
+        let mut transposition_table = HashMap::new();
+        let scores = move_scores(&game, &mut transposition_table, None).unwrap();
+        for (m, score) in scores.map(Result::unwrap) {
+            let mut child = game.clone();
+            child.make_move(&m).unwrap();
+
+            // moving to a losing position wins
+            assert_eq!(table[&child], score <= 0, "{m:?}");
+        }
+    }
+
+    #[test]
+    fn from_board() {
+        let mut played = Chomp::new(4, 3);
+        played.make_move(&NaturalMove([2, 1])).unwrap();
+        played.make_move(&NaturalMove([1, 0])).unwrap();
+
+        // X... on top, then XX.., then XXXX, as columns from the left
+        let board = Array2D::from_rows(&[
+            vec![true, true, true],
+            vec![false, true, true],
+            vec![false, false, true],
+            vec![false, false, true],
+        ])
+        .unwrap();
+        let game = Chomp::from_board(4, 3, board.clone()).unwrap();
+        assert_eq!(game.row_lengths(), played.row_lengths());
+        assert_eq!(
+            solve(&game, &mut HashMap::new(), SolveMode::Strong, None).unwrap() > 0,
+            solve(&played, &mut HashMap::new(), SolveMode::Strong, None).unwrap() > 0
+        );
+
+        // a square above an eaten square can't be left uneaten
+        let mut hole = board.clone();
+        hole[(1, 2)] = false;
+        assert!(Chomp::from_board(4, 3, hole).is_err());
+
+        let mut poisoned = board.clone();
+        poisoned[(0, 2)] = false;
+        assert!(Chomp::from_board(4, 3, poisoned).is_err());
+
+        assert!(Chomp::from_board(3, 4, board).is_err());
+    }
+
+    #[test]
+    fn invalid_dimensions() {
+        for (width, height) in [(3, 0), (0, 3), (usize::MAX, 2)] {
+            let args = ChompArgs {
+                width,
+                height,
+                moves: vec![],
+            };
+            assert!(Chomp::try_from(args).is_err());
+        }
+    }
+
+    #[test]
+    fn moves_round_trip() {
+        crate::util::assert_moves_round_trip(&Chomp::new(6, 4), 2);
+    }
+
+    #[test]
+    fn resolvable_games_match() {
+        for (width, height) in [(4, 3), (1, 4), (4, 1)] {
+            crate::util::assert_resolvable_games_match(&Chomp::new(width, height), 4);
+        }
+    }
 }
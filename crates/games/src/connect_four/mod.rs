@@ -0,0 +1,357 @@
+#![doc = include_str!("./README.md")]
+
+#[cfg(feature = "egui")]
+pub mod gui;
+use anyhow::{anyhow, Error};
+use clap::Args;
+use game_solver::{
+    game::{Game, GameState},
+    player::PartizanPlayer,
+};
+use ndarray::{ArrayD, IntoDimension, IxDyn};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{Debug, Display, Formatter},
+    hash::Hash,
+    str::FromStr,
+};
+use thiserror::Error;
+
+use crate::{
+    tic_tac_toe::{offsets, winning_line, Square},
+    util::move_failable,
+};
+
+#[derive(Clone, Hash, Eq, PartialEq)]
+pub struct ConnectFour {
+    width: usize,
+    height: usize,
+    win_length: usize,
+    /// Indexed by (column, row), where row 0 is the bottom of the board.
+    board: ArrayD<Option<Square>>,
+    move_count: usize,
+}
+
+/// Drops a piece into a column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct ConnectFourMove(pub usize);
+
+#[derive(Error, Debug, Clone)]
+pub enum ConnectFourMoveError {
+    #[error("column {column} is out of bounds of the amount of columns {width}.")]
+    OutOfBounds { column: usize, width: usize },
+    #[error("column {0} is already full.")]
+    ColumnFull(usize),
+}
+
+impl ConnectFour {
+    /// Create a new, empty board.
+    ///
+    /// # Errors
+    ///
+    /// If the board is empty, or a line of `win_length` pieces can't fit on it.
+    pub fn new(width: usize, height: usize, win_length: usize) -> Result<Self, Error> {
+        if width == 0 || height == 0 {
+            return Err(anyhow!(
+                "the board needs at least one column and row, but it was {width}x{height}."
+            ));
+        }
+
+        if win_length == 0 || win_length > width.max(height) {
+            return Err(anyhow!(
+                "the win length has to be between 1 and {}, but it was {win_length}.",
+                width.max(height)
+            ));
+        }
+
+        Ok(Self {
+            width,
+            height,
+            win_length,
+            board: ArrayD::from_elem(IxDyn(&[width, height]), None),
+            move_count: 0,
+        })
+    }
+
+    /// Returns the lowest empty row in `column`, if the column isn't full.
+    fn lowest_empty_row(&self, column: usize) -> Option<usize> {
+        (0..self.height).find(|&row| self.board[&[column, row][..]].is_none())
+    }
+}
+
+impl Game for ConnectFour {
+    type Move = ConnectFourMove;
+    type Iter<'a> = std::vec::IntoIter<Self::Move>;
+    type Player = PartizanPlayer;
+    type Key = Self;
+    type MoveError = ConnectFourMoveError;
+
+    fn max_moves(&self) -> Option<usize> {
+        Some(self.width * self.height)
+    }
+
+    fn move_count(&self) -> usize {
+        self.move_count
+    }
+
+    fn make_move(&mut self, m: &Self::Move) -> Result<(), Self::MoveError> {
+        if m.0 >= self.width {
+            return Err(ConnectFourMoveError::OutOfBounds {
+                column: m.0,
+                width: self.width,
+            });
+        }
+
+        let row = self
+            .lowest_empty_row(m.0)
+            .ok_or(ConnectFourMoveError::ColumnFull(m.0))?;
+
+        let square = match self.player() {
+            PartizanPlayer::Left => Square::X,
+            PartizanPlayer::Right => Square::O,
+        };
+
+        self.board[&[m.0, row][..]] = Some(square);
+        self.move_count += 1;
+
+        Ok(())
+    }
+
+    fn possible_moves(&self) -> Self::Iter<'_> {
+        // center columns are part of the most lines, so they're tried first
+        let mut columns = (0..self.width)
+            .filter(|&column| self.lowest_empty_row(column).is_some())
+            .collect::<Vec<_>>();
+        columns.sort_by_key(|&column| (2 * column).abs_diff(self.width - 1));
+
+        columns
+            .into_iter()
+            .map(ConnectFourMove)
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn state(&self) -> GameState<Self::Player> {
+        for (index, square) in self.board.indexed_iter() {
+            if square.is_none() {
+                continue;
+            }
+
+            let point = index.into_dimension();
+            for offset in offsets(&point, self.board.shape()) {
+                if let Some(square) = winning_line(&self.board, &point, &offset, self.win_length) {
+                    return GameState::Win(match square {
+                        Square::X => PartizanPlayer::Left,
+                        Square::O => PartizanPlayer::Right,
+                    });
+                }
+            }
+        }
+
+        if Some(self.move_count()) == self.max_moves() {
+            return GameState::Tie;
+        }
+
+        GameState::Playable
+    }
+
+    fn find_immediately_resolvable_game(&self) -> Result<Option<Self>, Self::MoveError> {
+        // a line needs at least win_length pieces from one player
+        if self.move_count + 2 < self.win_length * 2 {
+            return Ok(None);
+        }
+
+        for m in &mut self.possible_moves() {
+            let mut new_self = self.clone();
+            new_self.make_move(&m)?;
+            if new_self.state() == GameState::Win(self.player()) {
+                return Ok(Some(new_self));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn player(&self) -> Self::Player {
+        if self.move_count % 2 == 0 {
+            PartizanPlayer::Left
+        } else {
+            PartizanPlayer::Right
+        }
+    }
+}
+
+impl FromStr for ConnectFourMove {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(s.parse()?))
+    }
+}
+
+impl Display for ConnectFourMove {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Display for ConnectFour {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        writeln!(
+            f,
+            "Current player: {}",
+            match self.player() {
+                PartizanPlayer::Left => 'X',
+                PartizanPlayer::Right => 'O',
+            }
+        )?;
+
+        for row in (0..self.height).rev() {
+            for column in 0..self.width {
+                match self.board[&[column, row][..]] {
+                    Some(Square::X) => write!(f, "X")?,
+                    Some(Square::O) => write!(f, "O")?,
+                    None => write!(f, "-")?,
+                }
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Debug for ConnectFour {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        <Self as Display>::fmt(self, f)
+    }
+}
+
+/// Analyzes Connect Four.
+///
+#[doc = include_str!("./README.md")]
+#[derive(Args, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct ConnectFourArgs {
+    /// The amount of columns on the board
+    #[arg(long, default_value_t = 7)]
+    width: usize,
+    /// The amount of rows on the board
+    #[arg(long, default_value_t = 6)]
+    height: usize,
+    /// The amount of pieces in a row needed to win
+    #[arg(long, default_value_t = 4)]
+    win_length: usize,
+    /// The columns to drop pieces in, ordered as c1 c2 ...
+    #[arg(value_parser = clap::value_parser!(ConnectFourMove))]
+    moves: Vec<ConnectFourMove>,
+}
+
+impl Default for ConnectFourArgs {
+    fn default() -> Self {
+        Self {
+            width: 7,
+            height: 6,
+            win_length: 4,
+            moves: vec![],
+        }
+    }
+}
+
+impl TryFrom<ConnectFourArgs> for ConnectFour {
+    type Error = Error;
+
+    fn try_from(args: ConnectFourArgs) -> Result<Self, Self::Error> {
+        let mut game = ConnectFour::new(args.width, args.height, args.win_length)?;
+
+        for m in args.moves {
+            move_failable(&mut game, &m)?;
+        }
+
+        Ok(game)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use game_solver::{solve, SolveMode};
+
+    use super::*;
+
+    fn play(columns: &[usize]) -> ConnectFour {
+        let mut game = ConnectFour::new(7, 6, 4).unwrap();
+        for &column in columns {
+            game.make_move(&ConnectFourMove(column)).unwrap();
+        }
+        game
+    }
+
+    #[test]
+    fn pieces_fall() {
+        let game = play(&[3, 3]);
+
+        assert_eq!(game.board[&[3, 0][..]], Some(Square::X));
+        assert_eq!(game.board[&[3, 1][..]], Some(Square::O));
+        assert_eq!(game.board[&[3, 2][..]], None);
+        assert_eq!(game.max_moves(), Some(42));
+    }
+
+    #[test]
+    fn full_column() {
+        let mut game = play(&[0, 0, 0, 0, 0, 0]);
+
+        assert_eq!(game.possible_moves().len(), 6);
+        assert!(game.clone().make_move(&ConnectFourMove(0)).is_err());
+        assert!(game.make_move(&ConnectFourMove(7)).is_err());
+    }
+
+    #[test]
+    fn vertical_win() {
+        let game = play(&[0, 1, 0, 1, 0, 1]);
+        assert_eq!(game.state(), GameState::Playable);
+
+        let game = play(&[0, 1, 0, 1, 0, 1, 0]);
+        assert_eq!(game.state(), GameState::Win(PartizanPlayer::Left));
+    }
+
+    #[test]
+    fn diagonal_win() {
+        // X builds a staircase from the bottom left
+        let game = play(&[0, 1, 1, 2, 2, 3, 2, 3, 3, 6]);
+        assert_eq!(game.state(), GameState::Playable);
+
+        let game = play(&[0, 1, 1, 2, 2, 3, 2, 3, 3, 6, 3]);
+        assert_eq!(game.state(), GameState::Win(PartizanPlayer::Left));
+
+        // and the anti-diagonal, for O
+        let game = play(&[0, 6, 5, 5, 4, 4, 3, 4, 3, 3, 0, 3]);
+        assert_eq!(game.state(), GameState::Win(PartizanPlayer::Right));
+    }
+
+    #[test]
+    fn small_board_tie() {
+        // 4x4 connect four is a tie with perfect play
+        let game = ConnectFour::new(4, 4, 4).unwrap();
+        assert_eq!(
+            solve(&game, &mut HashMap::new(), SolveMode::Strong, None).unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn moves_round_trip() {
+        crate::util::assert_moves_round_trip(&ConnectFour::new(7, 6, 4).unwrap(), 2);
+    }
+
+    #[test]
+    fn invalid_boards() {
+        assert!(ConnectFour::new(0, 6, 4).is_err());
+        assert!(ConnectFour::new(7, 0, 4).is_err());
+        assert!(ConnectFour::new(7, 6, 0).is_err());
+        assert!(ConnectFour::new(7, 6, 8).is_err());
+
+        // a line only has to fit one way
+        assert!(ConnectFour::new(7, 1, 7).is_ok());
+    }
+}
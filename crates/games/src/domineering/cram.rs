@@ -0,0 +1,308 @@
+//! Cram is the impartial version of Domineering:
+//! both players can place their dominoes horizontally or vertically,
+//! and the last player to place a domino wins.
+
+use anyhow::{anyhow, Error};
+use array2d::Array2D;
+use game_solver::{
+    game::{Game, GameState, Normal, NormalImpartial},
+    player::ImpartialPlayer,
+};
+use std::{
+    fmt::{Debug, Display, Formatter},
+    hash::Hash,
+    str::FromStr,
+};
+use thiserror::Error;
+
+use crate::util::move_failable;
+
+use super::{
+    check_dimensions, fmt_board, place, possible_moves, remove, DomineeringArgs, DomineeringMove,
+    DomineeringVariant, Orientation,
+};
+
+#[derive(Clone, Hash, Eq, PartialEq)]
+pub struct Cram {
+    width: usize,
+    height: usize,
+    /// True represents a square - true if empty, false otherwise
+    board: Array2D<bool>,
+    move_count: usize,
+}
+
+impl Cram {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            board: Array2D::filled_with(true, width, height),
+            move_count: 0,
+        }
+    }
+}
+
+/// A domino placed at `position`, extending right if horizontal or down if vertical.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CramMove {
+    pub position: DomineeringMove,
+    pub orientation: Orientation,
+}
+
+impl Display for CramMove {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let orientation = match self.orientation {
+            Orientation::Horizontal => "h",
+            Orientation::Vertical => "v",
+        };
+
+        write!(f, "{}-{orientation}", self.position)
+    }
+}
+
+impl FromStr for CramMove {
+    type Err = Error;
+
+    /// Parses moves written as `x-y-h` for horizontal dominoes, or `x-y-v` for vertical ones.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (position, orientation) = s
+            .rsplit_once('-')
+            .ok_or_else(|| anyhow!("expected a move like 0-0-h, but got {s}"))?;
+
+        let orientation = match orientation {
+            "h" => Orientation::Horizontal,
+            "v" => Orientation::Vertical,
+            _ => {
+                return Err(anyhow!(
+                    "expected h or v as the orientation, but got {orientation}"
+                ))
+            }
+        };
+
+        Ok(CramMove {
+            position: DomineeringMove::from_str(position)?,
+            orientation,
+        })
+    }
+}
+
+#[derive(Error, Debug, Clone)]
+pub enum CramMoveError {
+    #[error("While no domino is present at {0}, a domino can not be placed at {0} because it would extend past the board or onto another domino.")]
+    BlockingAdjacent(CramMove),
+    #[error("A domino can not be placed at {0} because a domino is already at {0}.")]
+    BlockingCurrent(CramMove),
+    #[error("A domino can not be placed at {0} because it is off the board.")]
+    OutOfBounds(CramMove),
+}
+
+impl Normal for Cram {}
+impl NormalImpartial for Cram {}
+
+impl Game for Cram {
+    type Move = CramMove;
+    type Iter<'a> = std::vec::IntoIter<Self::Move>;
+    type Player = ImpartialPlayer;
+    type Key = Self;
+    type MoveError = CramMoveError;
+
+    fn max_moves(&self) -> Option<usize> {
+        Some(self.width * self.height / 2)
+    }
+
+    fn move_count(&self) -> usize {
+        self.move_count
+    }
+
+    fn make_move(&mut self, m: &Self::Move) -> Result<(), Self::MoveError> {
+        let DomineeringMove(x, y) = m.position;
+        let Some(&empty) = self.board.get(x, y) else {
+            return Err(CramMoveError::OutOfBounds(m.clone()));
+        };
+
+        if empty {
+            if !place(
+                &mut self.board,
+                (self.width, self.height),
+                &m.position,
+                m.orientation,
+            ) {
+                return Err(CramMoveError::BlockingAdjacent(m.clone()));
+            }
+
+            self.move_count += 1;
+            Ok(())
+        } else {
+            Err(CramMoveError::BlockingCurrent(m.clone()))
+        }
+    }
+
+    const SUPPORTS_UNDO: bool = true;
+
+    fn undo_move(&mut self, m: &Self::Move) -> Result<(), Self::MoveError> {
+        self.move_count -= 1;
+        remove(&mut self.board, &m.position, m.orientation);
+        Ok(())
+    }
+
+    fn possible_moves(&self) -> Self::Iter<'_> {
+        let size = (self.width, self.height);
+
+        [Orientation::Horizontal, Orientation::Vertical]
+            .into_iter()
+            .flat_map(|orientation| {
+                possible_moves(&self.board, size, orientation)
+                    .into_iter()
+                    .map(move |position| CramMove {
+                        position,
+                        orientation,
+                    })
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn state(&self) -> GameState<Self::Player> {
+        <Self as Normal>::state(self)
+    }
+
+    fn player(&self) -> Self::Player {
+        ImpartialPlayer::Next
+    }
+}
+
+impl Display for Cram {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        fmt_board(&self.board, (self.width, self.height), f)
+    }
+}
+
+impl Debug for Cram {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        <Self as Display>::fmt(self, f)
+    }
+}
+
+impl TryFrom<DomineeringArgs> for Cram {
+    type Error = Error;
+
+    fn try_from(args: DomineeringArgs) -> Result<Self, Self::Error> {
+        if args.variant != DomineeringVariant::Cram {
+            return Err(anyhow!(
+                "{:?} Domineering is played with DomineeringDyn instead.",
+                args.variant
+            ));
+        }
+
+        check_dimensions(args.width, args.height)?;
+
+        let mut game = Cram::new(args.width, args.height);
+
+        // parse every move in args, e.g. 0-0-h 1-1-v in args
+        for arg in args.moves {
+            move_failable(&mut game, &CramMove::from_str(&arg)?)?;
+        }
+
+        Ok(game)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use game_solver::{grundy_value, solve, SolveMode};
+    use nimnim::Nimber;
+
+    use super::*;
+
+    fn first_player_wins(width: usize, height: usize) -> bool {
+        let game = Cram::new(width, height);
+        let score = solve(&game, &mut HashMap::new(), SolveMode::Weak, None).unwrap();
+        let nimber = grundy_value(&game, &mut HashMap::new()).unwrap();
+
+        // the first player wins exactly when the game isn't worth zero
+        assert_eq!(score > 0, nimber != Nimber(0));
+        score > 0
+    }
+
+    #[test]
+    fn known_outcomes() {
+        // the second player wins even by even boards by mirroring through the center,
+        assert!(!first_player_wins(2, 2));
+        assert!(!first_player_wins(4, 4));
+        // and the first player wins even by odd boards by taking the center and then mirroring
+        assert!(first_player_wins(2, 3));
+        assert!(first_player_wins(3, 4));
+        // odd by odd boards have no symmetry strategy, but 3x3 is still a second player win
+        assert!(!first_player_wins(3, 3));
+    }
+
+    #[test]
+    fn moves_round_trip() {
+        for orientation in [Orientation::Horizontal, Orientation::Vertical] {
+            let m = CramMove {
+                position: DomineeringMove(2, 3),
+                orientation,
+            };
+            assert_eq!(CramMove::from_str(&m.to_string()).unwrap(), m);
+        }
+
+        assert!(CramMove::from_str("2-3-d").is_err());
+    }
+
+    #[test]
+    fn overlapping_placements() {
+        let at = |x, y, orientation| CramMove {
+            position: DomineeringMove(x, y),
+            orientation,
+        };
+
+        let mut game = Cram::new(4, 4);
+        game.make_move(&at(1, 0, Orientation::Horizontal)).unwrap();
+        assert!(matches!(
+            game.clone().make_move(&at(0, 0, Orientation::Horizontal)),
+            Err(CramMoveError::BlockingAdjacent(_))
+        ));
+        assert!(matches!(
+            game.clone().make_move(&at(2, 0, Orientation::Vertical)),
+            Err(CramMoveError::BlockingCurrent(_))
+        ));
+        game.make_move(&at(0, 0, Orientation::Vertical)).unwrap();
+    }
+
+    #[test]
+    fn empty_boards_are_rejected() {
+        for (width, height) in [(0, 3), (3, 0)] {
+            let args = DomineeringArgs {
+                width,
+                height,
+                variant: DomineeringVariant::Cram,
+                moves: vec![],
+            };
+            assert!(Cram::try_from(args).is_err());
+        }
+
+        assert!(matches!(
+            Cram::new(3, 3).make_move(&CramMove {
+                position: DomineeringMove(5, 5),
+                orientation: Orientation::Vertical,
+            }),
+            Err(CramMoveError::OutOfBounds(_))
+        ));
+    }
+
+    #[test]
+    fn args_pick_the_variant() {
+        let args = DomineeringArgs {
+            width: 3,
+            height: 2,
+            variant: DomineeringVariant::Cram,
+            moves: vec!["0-0-h".to_string(), "0-1-h".to_string()],
+        };
+        let game = Cram::try_from(args.clone()).unwrap();
+
+        assert_eq!(game.possible_moves().count(), 1);
+        assert!(crate::domineering::DomineeringDyn::try_from(args).is_err());
+    }
+}
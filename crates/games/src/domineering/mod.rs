@@ -1,22 +1,30 @@
 #![doc = include_str!("./README.md")]
 
+pub mod cram;
 #[cfg(feature = "egui")]
 pub mod gui;
-use anyhow::Error;
+use anyhow::{anyhow, Error};
 use array2d::Array2D;
-use clap::Args;
+use clap::{Args, ValueEnum};
 use game_solver::{
-    game::{Game, GameState, Normal},
+    game::{naive_immediately_resolvable_game, Game, GameState, Normal, StateType},
     player::PartizanPlayer,
 };
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::HashMap,
     fmt::{Debug, Display, Formatter},
-    hash::Hash, str::FromStr,
+    hash::Hash,
+    str::FromStr,
 };
 use thiserror::Error;
+use traditional_game::{Game as CgtGame, VecGame};
 
-use crate::util::move_failable;
+use crate::util::{
+    bitboard::{BitBoard, BoolGrid},
+    move_failable,
+    move_natural::NaturalMove,
+};
 
 #[derive(Clone, Hash, Eq, PartialEq, Debug, Copy)]
 pub enum Orientation {
@@ -35,12 +43,13 @@ impl Orientation {
 
 #[derive(Clone, Hash, Eq, PartialEq)]
 pub struct Domineering<const WIDTH: usize, const HEIGHT: usize> {
-    // TODO: bit array 2d
     /// True represents a square - true if empty, false otherwise
-    board: Array2D<bool>,
+    board: BitBoard,
     move_count: usize,
     /// The orientation the first player will play as.
     primary_orientation: Orientation,
+    /// Whether the last player to place a domino loses, instead of winning.
+    misere: bool,
 }
 
 impl<const WIDTH: usize, const HEIGHT: usize> Default for Domineering<WIDTH, HEIGHT> {
@@ -54,11 +63,24 @@ impl<const WIDTH: usize, const HEIGHT: usize> Domineering<WIDTH, HEIGHT> {
         Self::new_orientation(Orientation::Vertical)
     }
 
+    /// # Panics
+    ///
+    /// If the board has more than [`BitBoard::MAX_CELLS`] squares - use [`DomineeringDyn`] instead.
     pub fn new_orientation(orientation: Orientation) -> Self {
         Self {
-            board: Array2D::filled_with(true, WIDTH, HEIGHT),
+            board: BitBoard::filled_with(true, WIDTH, HEIGHT),
             move_count: 0,
             primary_orientation: orientation,
+            misere: false,
+        }
+    }
+
+    /// Create a new game of misère Domineering,
+    /// where the player who places the last domino loses.
+    pub fn new_misere() -> Self {
+        Self {
+            misere: true,
+            ..Self::new()
         }
     }
 }
@@ -69,6 +91,8 @@ pub enum DomineeringMoveError {
     BlockingAdjacent(DomineeringMove, PartizanPlayer),
     #[error("Player {1:?} can not move at {0} because a domino is already at {0}.")]
     BlockingCurrent(DomineeringMove, PartizanPlayer),
+    #[error("Player {1:?} can not move at {0} because it is off the board.")]
+    OutOfBounds(DomineeringMove, PartizanPlayer),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -76,40 +100,176 @@ pub struct DomineeringMove(usize, usize);
 
 impl Display for DomineeringMove {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        Debug::fmt(&self, f)
+        write!(f, "{}-{}", self.0, self.1)
     }
 }
 
-impl<const WIDTH: usize, const HEIGHT: usize> Domineering<WIDTH, HEIGHT> {
-    fn place(
-        &mut self,
-        m: &DomineeringMove,
-        orientation: Orientation,
-    ) -> Result<(), DomineeringMoveError> {
-        match orientation {
-            Orientation::Horizontal => {
-                if m.0 == WIDTH - 1 {
-                    return Err(DomineeringMoveError::BlockingAdjacent(
-                        m.clone(),
-                        self.player(),
-                    ));
+/// Places a domino on `board` at `m`, extending right or down depending on `orientation`.
+///
+/// Returns false without placing the domino if it would extend past the edge of the board,
+/// or if either of its squares is already covered.
+fn place(
+    board: &mut impl BoolGrid,
+    (width, height): (usize, usize),
+    m: &DomineeringMove,
+    orientation: Orientation,
+) -> bool {
+    let other = match orientation {
+        Orientation::Horizontal => (m.0 + 1, m.1),
+        Orientation::Vertical => (m.0, m.1 + 1),
+    };
+
+    if other.0 >= width
+        || other.1 >= height
+        || board.get_cell(m.0, m.1) != Some(true)
+        || board.get_cell(other.0, other.1) != Some(true)
+    {
+        return false;
+    }
+
+    board.set_cell(m.0, m.1, false).unwrap();
+    board.set_cell(other.0, other.1, false).unwrap();
+
+    true
+}
+
+/// Removes the domino of `orientation` placed on `board` at `m`, undoing [`place`].
+fn remove(board: &mut impl BoolGrid, m: &DomineeringMove, orientation: Orientation) {
+    let other = match orientation {
+        Orientation::Horizontal => (m.0 + 1, m.1),
+        Orientation::Vertical => (m.0, m.1 + 1),
+    };
+
+    board.set_cell(m.0, m.1, true).unwrap();
+    board.set_cell(other.0, other.1, true).unwrap();
+}
+
+/// Whether placing a domino of `orientation` on `board` could end the game.
+///
+/// The game only ends once the other player has nowhere to place their dominoes,
+/// and a domino covers two squares that are each part of at most two of their places.
+fn can_end_game(board: &impl BoolGrid, size: (usize, usize), orientation: Orientation) -> bool {
+    possible_moves(board, size, orientation.turn()).len() <= 4
+}
+
+/// Checks that a board of `width` by `height` has any squares at all,
+/// as every board is at least one square wide and tall.
+fn check_dimensions(width: usize, height: usize) -> Result<(), Error> {
+    if width == 0 || height == 0 {
+        return Err(anyhow!(
+            "a {width}x{height} board has no squares - both dimensions must be at least 1."
+        ));
+    }
+
+    Ok(())
+}
+
+/// Returns every place a domino of `orientation` fits on `board`.
+fn possible_moves(
+    board: &impl BoolGrid,
+    (width, height): (usize, usize),
+    orientation: Orientation,
+) -> Vec<DomineeringMove> {
+    let mut moves = Vec::new();
+
+    match orientation {
+        Orientation::Horizontal => {
+            for i in 0..height {
+                for j in 0..width - 1 {
+                    if board.get_cell(j, i).unwrap() && board.get_cell(j + 1, i).unwrap() {
+                        moves.push(DomineeringMove(j, i));
+                    }
                 }
-                self.board.set(m.0, m.1, false).unwrap();
-                self.board.set(m.0 + 1, m.1, false).unwrap();
             }
-            Orientation::Vertical => {
-                if m.1 == HEIGHT - 1 {
-                    return Err(DomineeringMoveError::BlockingAdjacent(
-                        m.clone(),
-                        self.player(),
-                    ));
+        }
+        Orientation::Vertical => {
+            for i in 0..height - 1 {
+                for j in 0..width {
+                    if board.get_cell(j, i).unwrap() && board.get_cell(j, i + 1).unwrap() {
+                        moves.push(DomineeringMove(j, i));
+                    }
                 }
-                self.board.set(m.0, m.1, false).unwrap();
-                self.board.set(m.0, m.1 + 1, false).unwrap();
             }
-        };
+        }
+    }
 
-        Ok(())
+    moves
+}
+
+fn fmt_board(
+    board: &impl BoolGrid,
+    (width, height): (usize, usize),
+    f: &mut Formatter,
+) -> Result<(), std::fmt::Error> {
+    for i in 0..height {
+        for j in 0..width {
+            if board.get_cell(j, i).unwrap() {
+                write!(f, "X")?;
+            } else {
+                write!(f, ".")?;
+            }
+        }
+        writeln!(f)?;
+    }
+    Ok(())
+}
+
+/// The canonical Conway game value of `board`, where Left places dominoes in `left`'s orientation
+/// and Right in the other. The values of smaller boards are cached in `values`.
+fn game_value<B: BoolGrid + Clone + Eq + Hash>(
+    board: &B,
+    size: (usize, usize),
+    left: Orientation,
+    values: &mut HashMap<B, VecGame>,
+) -> VecGame {
+    if let Some(value) = values.get(board) {
+        return value.clone();
+    }
+
+    let mut options = |orientation: Orientation| {
+        possible_moves(board, size, orientation)
+            .iter()
+            .map(|m| {
+                let mut option = board.clone();
+                place(&mut option, size, m, orientation);
+                Box::new(game_value(&option, size, left, values)) as Box<dyn CgtGame>
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let left_options = options(left);
+    let right_options = options(left.turn());
+    let value = VecGame::new(left_options, right_options).canonicalize();
+
+    values.insert(board.clone(), value.clone());
+    value
+}
+
+impl<const WIDTH: usize, const HEIGHT: usize> Domineering<WIDTH, HEIGHT> {
+    /// The canonical Conway game value of this position, where Left is the first player.
+    ///
+    /// Unlike the solver, this doesn't depend on whose turn it is, so it can be used to
+    /// compare positions or sum them with other games.
+    ///
+    /// Game values only exist under normal play, so misère positions have none.
+    pub fn game_value(&self) -> Option<VecGame> {
+        (!self.misere).then(|| {
+            game_value(
+                &self.board,
+                (WIDTH, HEIGHT),
+                self.primary_orientation,
+                &mut HashMap::new(),
+            )
+        })
+    }
+
+    /// The orientation the current player places dominoes in.
+    fn orientation(&self) -> Orientation {
+        if self.player() == PartizanPlayer::Left {
+            self.primary_orientation
+        } else {
+            self.primary_orientation.turn()
+        }
     }
 }
 
@@ -119,6 +279,7 @@ impl<const WIDTH: usize, const HEIGHT: usize> Game for Domineering<WIDTH, HEIGHT
     type Move = DomineeringMove;
     type Iter<'a> = std::vec::IntoIter<Self::Move>;
     type Player = PartizanPlayer;
+    type Key = Self;
     type MoveError = DomineeringMoveError;
 
     fn max_moves(&self) -> Option<usize> {
@@ -130,15 +291,18 @@ impl<const WIDTH: usize, const HEIGHT: usize> Game for Domineering<WIDTH, HEIGHT
     }
 
     fn make_move(&mut self, m: &Self::Move) -> Result<(), Self::MoveError> {
-        if *self.board.get(m.0, m.1).unwrap() {
-            self.place(
-                m,
-                if self.player() == PartizanPlayer::Left {
-                    self.primary_orientation
-                } else {
-                    self.primary_orientation.turn()
-                },
-            )?;
+        let Some(empty) = self.board.get_cell(m.0, m.1) else {
+            return Err(DomineeringMoveError::OutOfBounds(m.clone(), self.player()));
+        };
+
+        if empty {
+            let orientation = self.orientation();
+            if !place(&mut self.board, (WIDTH, HEIGHT), m, orientation) {
+                return Err(DomineeringMoveError::BlockingAdjacent(
+                    m.clone(),
+                    self.player(),
+                ));
+            }
 
             self.move_count += 1;
             Ok(())
@@ -150,40 +314,191 @@ impl<const WIDTH: usize, const HEIGHT: usize> Game for Domineering<WIDTH, HEIGHT
         }
     }
 
+    const SUPPORTS_UNDO: bool = true;
+
+    fn undo_move(&mut self, m: &Self::Move) -> Result<(), Self::MoveError> {
+        self.move_count -= 1;
+        let orientation = self.orientation();
+        remove(&mut self.board, m, orientation);
+        Ok(())
+    }
+
     fn possible_moves(&self) -> Self::Iter<'_> {
-        let mut moves = Vec::new();
-        let orientation = if self.player() == PartizanPlayer::Left {
+        possible_moves(&self.board, (WIDTH, HEIGHT), self.orientation()).into_iter()
+    }
+
+    fn find_immediately_resolvable_game(&self) -> Result<Option<Self>, Self::MoveError> {
+        if !can_end_game(&self.board, (WIDTH, HEIGHT), self.orientation()) {
+            return Ok(None);
+        }
+
+        naive_immediately_resolvable_game(self)
+    }
+
+    fn state(&self) -> GameState<Self::Player> {
+        if self.misere {
+            StateType::Misere.state(self)
+        } else {
+            <Self as Normal>::state(self)
+        }
+    }
+
+    fn player(&self) -> Self::Player {
+        if self.move_count % 2 == 0 {
+            PartizanPlayer::Left
+        } else {
+            PartizanPlayer::Right
+        }
+    }
+}
+
+impl<const WIDTH: usize, const HEIGHT: usize> Display for Domineering<WIDTH, HEIGHT> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        fmt_board(&self.board, (WIDTH, HEIGHT), f)
+    }
+}
+
+impl<const WIDTH: usize, const HEIGHT: usize> Debug for Domineering<WIDTH, HEIGHT> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        <Self as Display>::fmt(self, f)
+    }
+}
+
+/// Domineering with dimensions chosen at runtime.
+///
+/// This plays exactly like [`Domineering`], which should be preferred
+/// when the dimensions are known at compile time, as it stores its board in a [`BitBoard`].
+/// This stores its board in an [`Array2D`] instead, so it isn't limited in size.
+#[derive(Clone, Hash, Eq, PartialEq)]
+pub struct DomineeringDyn {
+    width: usize,
+    height: usize,
+    /// True represents a square - true if empty, false otherwise
+    board: Array2D<bool>,
+    move_count: usize,
+    /// The orientation the first player will play as.
+    primary_orientation: Orientation,
+    /// Whether the last player to place a domino loses, instead of winning.
+    misere: bool,
+}
+
+impl DomineeringDyn {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self::new_orientation(width, height, Orientation::Vertical)
+    }
+
+    pub fn new_orientation(width: usize, height: usize, orientation: Orientation) -> Self {
+        Self {
+            width,
+            height,
+            board: Array2D::filled_with(true, width, height),
+            move_count: 0,
+            primary_orientation: orientation,
+            misere: false,
+        }
+    }
+
+    /// Create a new game of misère Domineering,
+    /// where the player who places the last domino loses.
+    pub fn new_misere(width: usize, height: usize) -> Self {
+        Self {
+            misere: true,
+            ..Self::new(width, height)
+        }
+    }
+
+    /// The canonical Conway game value of this position, where Left is the first player.
+    ///
+    /// See [`Domineering::game_value`] - misère positions have none.
+    pub fn game_value(&self) -> Option<VecGame> {
+        (!self.misere).then(|| {
+            game_value(
+                &self.board,
+                (self.width, self.height),
+                self.primary_orientation,
+                &mut HashMap::new(),
+            )
+        })
+    }
+
+    /// The orientation the current player places dominoes in.
+    fn orientation(&self) -> Orientation {
+        if self.player() == PartizanPlayer::Left {
             self.primary_orientation
         } else {
             self.primary_orientation.turn()
+        }
+    }
+}
+
+impl Normal for DomineeringDyn {}
+
+impl Game for DomineeringDyn {
+    type Move = DomineeringMove;
+    type Iter<'a> = std::vec::IntoIter<Self::Move>;
+    type Player = PartizanPlayer;
+    type Key = Self;
+    type MoveError = DomineeringMoveError;
+
+    fn max_moves(&self) -> Option<usize> {
+        Some(self.width * self.height)
+    }
+
+    fn move_count(&self) -> usize {
+        self.move_count
+    }
+
+    fn make_move(&mut self, m: &Self::Move) -> Result<(), Self::MoveError> {
+        let Some(&empty) = self.board.get(m.0, m.1) else {
+            return Err(DomineeringMoveError::OutOfBounds(m.clone(), self.player()));
         };
 
-        match orientation {
-            Orientation::Horizontal => {
-                for i in 0..HEIGHT {
-                    for j in 0..WIDTH - 1 {
-                        if *self.board.get(j, i).unwrap() && *self.board.get(j + 1, i).unwrap() {
-                            moves.push(DomineeringMove(j, i));
-                        }
-                    }
-                }
-            }
-            Orientation::Vertical => {
-                for i in 0..HEIGHT - 1 {
-                    for j in 0..WIDTH {
-                        if *self.board.get(j, i).unwrap() && *self.board.get(j, i + 1).unwrap() {
-                            moves.push(DomineeringMove(j, i));
-                        }
-                    }
-                }
+        if empty {
+            let orientation = self.orientation();
+            if !place(&mut self.board, (self.width, self.height), m, orientation) {
+                return Err(DomineeringMoveError::BlockingAdjacent(
+                    m.clone(),
+                    self.player(),
+                ));
             }
+
+            self.move_count += 1;
+            Ok(())
+        } else {
+            Err(DomineeringMoveError::BlockingCurrent(
+                m.clone(),
+                self.player(),
+            ))
         }
+    }
+
+    const SUPPORTS_UNDO: bool = true;
 
-        moves.into_iter()
+    fn undo_move(&mut self, m: &Self::Move) -> Result<(), Self::MoveError> {
+        self.move_count -= 1;
+        let orientation = self.orientation();
+        remove(&mut self.board, m, orientation);
+        Ok(())
+    }
+
+    fn possible_moves(&self) -> Self::Iter<'_> {
+        possible_moves(&self.board, (self.width, self.height), self.orientation()).into_iter()
+    }
+
+    fn find_immediately_resolvable_game(&self) -> Result<Option<Self>, Self::MoveError> {
+        if !can_end_game(&self.board, (self.width, self.height), self.orientation()) {
+            return Ok(None);
+        }
+
+        naive_immediately_resolvable_game(self)
     }
 
     fn state(&self) -> GameState<Self::Player> {
-        <Self as Normal>::state(&self)
+        if self.misere {
+            StateType::Misere.state(self)
+        } else {
+            <Self as Normal>::state(self)
+        }
     }
 
     fn player(&self) -> Self::Player {
@@ -195,23 +510,13 @@ impl<const WIDTH: usize, const HEIGHT: usize> Game for Domineering<WIDTH, HEIGHT
     }
 }
 
-impl<const WIDTH: usize, const HEIGHT: usize> Display for Domineering<WIDTH, HEIGHT> {
+impl Display for DomineeringDyn {
     fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
-        for i in 0..HEIGHT {
-            for j in 0..WIDTH {
-                if *self.board.get(j, i).unwrap() {
-                    write!(f, "X")?;
-                } else {
-                    write!(f, ".")?;
-                }
-            }
-            writeln!(f)?;
-        }
-        Ok(())
+        fmt_board(&self.board, (self.width, self.height), f)
     }
 }
 
-impl<const WIDTH: usize, const HEIGHT: usize> Debug for Domineering<WIDTH, HEIGHT> {
+impl Debug for DomineeringDyn {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         <Self as Display>::fmt(self, f)
     }
@@ -220,21 +525,56 @@ impl<const WIDTH: usize, const HEIGHT: usize> Debug for Domineering<WIDTH, HEIGH
 /// Analyzes Domineering.
 ///
 #[doc = include_str!("./README.md")]
-#[derive(Args, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Default)]
+#[derive(Args, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
 pub struct DomineeringArgs {
+    /// The width of the game
+    #[arg(long, default_value_t = 5)]
+    width: usize,
+    /// The height of the game
+    #[arg(long, default_value_t = 5)]
+    height: usize,
+    /// The rules to play by
+    #[arg(long, value_enum, default_value_t = DomineeringVariant::Normal)]
+    variant: DomineeringVariant,
+    /// Domineering moves, ordered as x1-y1 x2-y2 ...
     moves: Vec<String>,
 }
 
+impl DomineeringArgs {
+    pub fn variant(&self) -> DomineeringVariant {
+        self.variant
+    }
+}
+
+impl Default for DomineeringArgs {
+    fn default() -> Self {
+        Self {
+            width: 5,
+            height: 5,
+            variant: DomineeringVariant::Normal,
+            moves: vec![],
+        }
+    }
+}
+
+/// The rules Domineering can be played by.
+#[derive(ValueEnum, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+pub enum DomineeringVariant {
+    /// The last player to place a domino wins.
+    Normal,
+    /// The last player to place a domino loses.
+    Misere,
+    /// Both players can place dominoes in either orientation - see [`Cram`](cram::Cram).
+    Cram,
+}
+
 impl FromStr for DomineeringMove {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let numbers: Vec<usize> = s
-            .split('-')
-            .map(|num| num.parse::<usize>().expect("Not a number!"))
-            .collect();
+        let NaturalMove([x, y]) = NaturalMove::from_str(s)?;
 
-        Ok(DomineeringMove(numbers[0], numbers[1]))
+        Ok(DomineeringMove(x, y))
     }
 }
 
@@ -244,7 +584,40 @@ impl<const WIDTH: usize, const HEIGHT: usize> TryFrom<DomineeringArgs>
     type Error = Error;
 
     fn try_from(args: DomineeringArgs) -> Result<Self, Self::Error> {
-        let mut game = Domineering::new();
+        if args.width != WIDTH || args.height != HEIGHT {
+            return Err(anyhow!(
+                "expected a {WIDTH}x{HEIGHT} board, but got {}x{} - use DomineeringDyn instead.",
+                args.width,
+                args.height
+            ));
+        }
+
+        let mut game = match args.variant {
+            DomineeringVariant::Normal => Domineering::new(),
+            DomineeringVariant::Misere => Domineering::new_misere(),
+            DomineeringVariant::Cram => return Err(anyhow!("Cram is played with Cram instead.")),
+        };
+
+        // parse every move in args, e.g. 0-0 1-1 in args
+        for arg in args.moves {
+            move_failable(&mut game, &DomineeringMove::from_str(&arg)?)?;
+        }
+
+        Ok(game)
+    }
+}
+
+impl TryFrom<DomineeringArgs> for DomineeringDyn {
+    type Error = Error;
+
+    fn try_from(args: DomineeringArgs) -> Result<Self, Self::Error> {
+        check_dimensions(args.width, args.height)?;
+
+        let mut game = match args.variant {
+            DomineeringVariant::Normal => DomineeringDyn::new(args.width, args.height),
+            DomineeringVariant::Misere => DomineeringDyn::new_misere(args.width, args.height),
+            DomineeringVariant::Cram => return Err(anyhow!("Cram is played with Cram instead.")),
+        };
 
         // parse every move in args, e.g. 0-0 1-1 in args
         for arg in args.moves {
@@ -257,9 +630,10 @@ impl<const WIDTH: usize, const HEIGHT: usize> TryFrom<DomineeringArgs>
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
+    use std::{collections::HashMap, sync::atomic::Ordering, time::Instant};
 
-    use game_solver::{move_scores, GameSolveError};
+    use game_solver::{move_scores, solve, stats::Stats, GameSolveError, SolveMode};
+    use traditional_game::Outcome;
 
     use super::*;
 
@@ -269,6 +643,7 @@ mod tests {
     ) -> Option<PartizanPlayer> {
         let game = Domineering::<WIDTH, HEIGHT>::new_orientation(orientation);
         let mut move_scores = move_scores(&game, &mut HashMap::new(), None)
+            .unwrap()
             .collect::<Result<Vec<_>, GameSolveError<Domineering<WIDTH, HEIGHT>>>>()
             .unwrap();
 
@@ -309,10 +684,62 @@ mod tests {
         );
     }
 
+    /// The integer `n` as a game: `{n - 1|}`.
+    fn integer(n: usize) -> VecGame {
+        (0..n).fold(VecGame::zero(), |value, _| {
+            VecGame::new(vec![Box::new(value)], vec![])
+        })
+    }
+
+    #[test]
+    fn game_values() {
+        // a vertical strip of n squares holds n / 2 moves for Left, and none for Right
+        for n in 1..=6 {
+            let strip = DomineeringDyn::new(1, n);
+            assert!(CgtGame::eq(&strip.game_value().unwrap(), &integer(n / 2)));
+        }
+        assert!(CgtGame::eq(
+            &Domineering::<1, 4>::new().game_value().unwrap(),
+            &integer(2)
+        ));
+
+        // horizontal strips are the same for Right: {|0} = -1
+        let strip = DomineeringDyn::new(3, 1).game_value().unwrap();
+        assert!(CgtGame::eq(&strip, &integer(1).flip()));
+
+        // whoever moves first on a 2x2 board wins: {1|-1} = ±1
+        let value = Domineering::<2, 2>::new().game_value().unwrap();
+        assert!(CgtGame::eq(
+            &value,
+            &VecGame::singleton(integer(1), integer(1).flip())
+        ));
+        assert_eq!(value.outcome(), Outcome::Next);
+
+        assert!(Domineering::<2, 2>::new_misere().game_value().is_none());
+        assert!(DomineeringDyn::new_misere(2, 2).game_value().is_none());
+    }
+
+    #[test]
+    fn misere_flips_the_last_move() {
+        // only Left can place a domino on a column of two squares, which then leaves Right stuck
+        let normal = DomineeringDyn::new(1, 2);
+        let misere = DomineeringDyn::new_misere(1, 2);
+
+        let mut transposition_table = HashMap::new();
+        assert!(solve(&normal, &mut transposition_table, SolveMode::Weak, None).unwrap() > 0);
+        let mut transposition_table = HashMap::new();
+        assert!(solve(&misere, &mut transposition_table, SolveMode::Weak, None).unwrap() < 0);
+
+        let mut game = misere;
+        game.make_move(&DomineeringMove(0, 0)).unwrap();
+        assert_eq!(Game::state(&game), GameState::Win(PartizanPlayer::Right));
+    }
+
     #[test]
     fn test_domineering() {
         let game = Domineering::<5, 5>::new_orientation(Orientation::Horizontal);
         let mut move_scores = move_scores(&game, &mut HashMap::new(), None)
+            .unwrap()
             .collect::<Result<Vec<_>, GameSolveError<Domineering<5, 5>>>>()
             .unwrap();
 
@@ -347,4 +774,239 @@ mod tests {
 
         assert_eq!(move_scores, current_scores);
     }
+
+    #[test]
+    fn dyn_matches_const() {
+        for orientation in [Orientation::Horizontal, Orientation::Vertical] {
+            let game = Domineering::<4, 3>::new_orientation(orientation);
+            let dyn_game = DomineeringDyn::new_orientation(4, 3, orientation);
+
+            assert_eq!(
+                game.possible_moves().collect::<Vec<_>>(),
+                dyn_game.possible_moves().collect::<Vec<_>>()
+            );
+            assert_eq!(
+                solve(&game, &mut HashMap::new(), SolveMode::Strong, None).unwrap(),
+                solve(&dyn_game, &mut HashMap::new(), SolveMode::Strong, None).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn args_dimensions() {
+        let args = DomineeringArgs {
+            width: 3,
+            height: 2,
+            variant: DomineeringVariant::Normal,
+            moves: vec!["0-0".to_string()],
+        };
+
+        let game = DomineeringDyn::try_from(args.clone()).unwrap();
+        assert_eq!(game.max_moves(), Some(6));
+        assert_eq!(game.move_count(), 1);
+
+        assert!(Domineering::<3, 2>::try_from(args.clone()).is_ok());
+        assert!(Domineering::<5, 5>::try_from(args).is_err());
+    }
+
+    #[test]
+    fn exact_scores_skip_resolving() {
+        let game = Domineering::<4, 4>::new();
+        let mut transposition_table = HashMap::new();
+        let score = solve(&game, &mut transposition_table, SolveMode::Strong, None).unwrap();
+
+        let stats = Stats::new(game.player(), game.move_count());
+        assert_eq!(
+            solve(
+                &game,
+                &mut transposition_table,
+                SolveMode::Strong,
+                Some(&stats)
+            )
+            .unwrap(),
+            score
+        );
+
+        // every null window probe returns immediately at the root
+        assert!(stats.states_explored.load(Ordering::Relaxed) < 10);
+    }
+
+    /// Solves `game`, returning its score alongside how many states were explored per second.
+    fn states_per_second<T: Game<Player = PartizanPlayer> + Eq + Hash + Debug>(
+        game: &T,
+    ) -> (isize, f64) {
+        let stats = Stats::new(game.player(), game.move_count());
+
+        let start = Instant::now();
+        let score = solve(game, &mut HashMap::new(), SolveMode::Strong, Some(&stats)).unwrap();
+        let elapsed = start.elapsed().as_secs_f64();
+
+        (
+            score,
+            stats.states_explored.load(Ordering::Relaxed) as f64 / elapsed,
+        )
+    }
+
+    #[test]
+    #[ignore = "benchmark - run with `cargo test --release -- --ignored --nocapture`"]
+    fn bitboard_throughput() {
+        let (bitboard_score, bitboard) = states_per_second(&Domineering::<5, 5>::new());
+        let (array_score, array) = states_per_second(&DomineeringDyn::new(5, 5));
+
+        // timings are too noisy to assert on, so they're only reported
+        println!("5x5 domineering, states explored per second:");
+        println!("  bitboard (Domineering<5, 5>): {bitboard:.0}");
+        println!("  array (DomineeringDyn):       {array:.0}");
+        println!("  speedup: {:.2}x", bitboard / array);
+
+        assert_eq!(bitboard_score, array_score);
+    }
+
+    /// A move that is never equal to any other move, so it can't be a killer move.
+    #[derive(Clone, Debug)]
+    struct Unkillable(DomineeringMove);
+
+    impl PartialEq for Unkillable {
+        fn eq(&self, _other: &Self) -> bool {
+            false
+        }
+    }
+
+    /// Domineering, solved without the killer heuristic.
+    #[derive(Clone, Hash, Eq, PartialEq, Debug)]
+    struct WithoutKillers<const WIDTH: usize, const HEIGHT: usize>(Domineering<WIDTH, HEIGHT>);
+
+    impl<const WIDTH: usize, const HEIGHT: usize> Game for WithoutKillers<WIDTH, HEIGHT> {
+        type Move = Unkillable;
+        type Iter<'a> = std::vec::IntoIter<Self::Move>;
+        type Player = PartizanPlayer;
+        type Key = Self;
+        type MoveError = DomineeringMoveError;
+
+        fn max_moves(&self) -> Option<usize> {
+            self.0.max_moves()
+        }
+
+        fn move_count(&self) -> usize {
+            self.0.move_count()
+        }
+
+        fn make_move(&mut self, m: &Self::Move) -> Result<(), Self::MoveError> {
+            self.0.make_move(&m.0)
+        }
+
+        fn possible_moves(&self) -> Self::Iter<'_> {
+            self.0
+                .possible_moves()
+                .map(Unkillable)
+                .collect::<Vec<_>>()
+                .into_iter()
+        }
+
+        fn state(&self) -> GameState<Self::Player> {
+            Game::state(&self.0)
+        }
+
+        fn player(&self) -> Self::Player {
+            self.0.player()
+        }
+    }
+
+    /// Returns the amount of states explored per pruning cutoff while solving `game`.
+    fn states_per_cutoff<T: Game<Player = PartizanPlayer> + Eq + Hash + Debug>(game: &T) -> f64 {
+        let stats = Stats::new(game.player(), game.move_count());
+        solve(game, &mut HashMap::new(), SolveMode::Strong, Some(&stats)).unwrap();
+
+        stats.states_explored.load(Ordering::Relaxed) as f64
+            / stats.pruning_cutoffs.load(Ordering::Relaxed) as f64
+    }
+
+    #[test]
+    fn killer_moves_reduce_search() {
+        let game = Domineering::<4, 6>::new();
+
+        let with_killers = states_per_cutoff(&game);
+        let without_killers = states_per_cutoff(&WithoutKillers(game));
+
+        assert!(with_killers < without_killers);
+    }
+
+    #[test]
+    fn moves_round_trip() {
+        crate::util::assert_moves_round_trip(&Domineering::<5, 5>::new(), 2);
+    }
+
+    #[test]
+    fn overlapping_placements() {
+        // Left covers 1-0 and 1-1, so Right can't cover 0-0 and 1-0
+        let mut game = Domineering::<5, 5>::new();
+        game.make_move(&DomineeringMove(1, 0)).unwrap();
+        assert!(matches!(
+            game.clone().make_move(&DomineeringMove(0, 0)),
+            Err(DomineeringMoveError::BlockingAdjacent(..))
+        ));
+
+        let mut game = DomineeringDyn::new(5, 5);
+        game.make_move(&DomineeringMove(1, 0)).unwrap();
+        assert!(matches!(
+            game.clone().make_move(&DomineeringMove(0, 0)),
+            Err(DomineeringMoveError::BlockingAdjacent(..))
+        ));
+        game.make_move(&DomineeringMove(2, 0)).unwrap();
+    }
+
+    #[test]
+    fn empty_boards_are_rejected() {
+        for (width, height) in [(3, 0), (0, 3), (0, 0)] {
+            let args = DomineeringArgs {
+                width,
+                height,
+                ..DomineeringArgs::default()
+            };
+            assert!(DomineeringDyn::try_from(args).is_err());
+        }
+    }
+
+    #[test]
+    fn off_board_placements() {
+        assert!(matches!(
+            Domineering::<5, 5>::new().make_move(&DomineeringMove(9, 9)),
+            Err(DomineeringMoveError::OutOfBounds(..))
+        ));
+        assert!(matches!(
+            DomineeringDyn::new(5, 5).make_move(&DomineeringMove(9, 9)),
+            Err(DomineeringMoveError::OutOfBounds(..))
+        ));
+    }
+
+    #[test]
+    fn resolvable_games_match() {
+        crate::util::assert_resolvable_games_match(&Domineering::<3, 4>::new(), 5);
+        crate::util::assert_resolvable_games_match(&Domineering::<3, 3>::new_misere(), 5);
+        crate::util::assert_resolvable_games_match(&DomineeringDyn::new(4, 3), 5);
+    }
+
+    #[test]
+    fn undo_restores_the_game() {
+        fn assert_undoes<T: Game + Eq + Debug>(game: T) {
+            // both players' moves, since they place dominoes differently
+            for first in game.possible_moves() {
+                let mut board = game.clone();
+                board.make_move(&first).unwrap();
+                let after_first = board.clone();
+
+                for second in after_first.possible_moves() {
+                    board.make_move(&second).unwrap();
+                    board.undo_move(&second).unwrap();
+                    assert_eq!(board, after_first);
+                }
+
+                board.undo_move(&first).unwrap();
+                assert_eq!(board, game);
+            }
+        }
+
+        assert_undoes(Domineering::<4, 3>::new());
+        assert_undoes(DomineeringDyn::new(4, 3));
+    }
 }
@@ -0,0 +1,512 @@
+#![doc = include_str!("./README.md")]
+
+use anyhow::{anyhow, Error};
+use clap::Args;
+use game_solver::{
+    game::{Game, GameState},
+    player::{PartizanPlayer, Player},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{self, Debug, Display, Formatter},
+    hash::Hash,
+    str::FromStr,
+};
+use thiserror::Error;
+
+use crate::util::move_failable;
+
+/// Sows the seeds of a pit, numbered from `0` to `pits - 1` from the left of the player to move.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct KalahMove(pub usize);
+
+impl Display for KalahMove {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for KalahMove {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(s.parse()?))
+    }
+}
+
+#[derive(Error, Debug, Clone)]
+pub enum KalahMoveError {
+    #[error("there is no pit {0}.")]
+    NoPit(KalahMove),
+    #[error("pit {0} has no seeds to sow.")]
+    EmptyPit(KalahMove),
+}
+
+#[derive(Clone, Hash, Eq, PartialEq)]
+pub struct Kalah {
+    /// The amount of pits each player has
+    pits: usize,
+    /// The seeds in Left's pits, Left's store, Right's pits, then Right's store,
+    /// in the order seeds are sown.
+    board: Vec<usize>,
+    player: PartizanPlayer,
+    move_count: usize,
+}
+
+impl Kalah {
+    /// Create a new game of Kalah, where both players have `pits` pits of `seeds` seeds.
+    pub fn new(pits: usize, seeds: usize) -> Self {
+        let mut board = vec![seeds; 2 * pits + 2];
+        board[pits] = 0;
+        board[2 * pits + 1] = 0;
+
+        Self {
+            pits,
+            board,
+            player: PartizanPlayer::Left,
+            move_count: 0,
+        }
+    }
+
+    /// The index in the board of `player`'s store.
+    fn store(&self, player: PartizanPlayer) -> usize {
+        match player {
+            PartizanPlayer::Left => self.pits,
+            PartizanPlayer::Right => 2 * self.pits + 1,
+        }
+    }
+
+    /// The index in the board of `player`'s pit `pit`.
+    fn pit(&self, player: PartizanPlayer, pit: usize) -> usize {
+        match player {
+            PartizanPlayer::Left => pit,
+            PartizanPlayer::Right => self.pits + 1 + pit,
+        }
+    }
+
+    /// The seeds in `player`'s pits, from their left.
+    fn side(&self, player: PartizanPlayer) -> &[usize] {
+        let start = self.pit(player, 0);
+        &self.board[start..start + self.pits]
+    }
+
+    /// The amount of seeds in the stores of (Left, Right).
+    pub fn stores(&self) -> (usize, usize) {
+        (
+            self.board[self.store(PartizanPlayer::Left)],
+            self.board[self.store(PartizanPlayer::Right)],
+        )
+    }
+
+    /// The most moves a game that started with `seeds` in total can last.
+    ///
+    /// Every move either puts a seed in a store, which happens at most once per seed,
+    /// or only moves seeds to the right on the player's own side, which the player
+    /// can only do `pits * seeds` times before a seed has to be stored.
+    fn max_moves_with(pits: usize, seeds: usize) -> usize {
+        seeds + (seeds + 1) * 2 * pits * seeds
+    }
+
+    /// The index in the board the last seed of `m` lands in.
+    fn last_seed(&self, m: &KalahMove) -> usize {
+        let start = self.pit(self.player, m.0);
+        // the opponent's store is skipped, so sowing loops around every other pit
+        let offset = self.board[start] % (2 * self.pits + 1);
+
+        (0..offset).fold(start, |index, _| {
+            let next = (index + 1) % self.board.len();
+            if next == self.store(self.player.next()) {
+                (next + 1) % self.board.len()
+            } else {
+                next
+            }
+        })
+    }
+}
+
+impl Game for Kalah {
+    type Move = KalahMove;
+    type Iter<'a> = std::vec::IntoIter<Self::Move>;
+    type Player = PartizanPlayer;
+    type Key = Self;
+    type MoveError = KalahMoveError;
+
+    fn max_moves(&self) -> Option<usize> {
+        Some(Self::max_moves_with(self.pits, self.board.iter().sum()))
+    }
+
+    fn move_count(&self) -> usize {
+        self.move_count
+    }
+
+    fn make_move(&mut self, m: &Self::Move) -> Result<(), Self::MoveError> {
+        if m.0 >= self.pits {
+            return Err(KalahMoveError::NoPit(*m));
+        }
+
+        let start = self.pit(self.player, m.0);
+        if self.board[start] == 0 {
+            return Err(KalahMoveError::EmptyPit(*m));
+        }
+
+        let last = self.last_seed(m);
+        let opponent_store = self.store(self.player.next());
+
+        let mut seeds = std::mem::take(&mut self.board[start]);
+        let mut index = start;
+        while seeds > 0 {
+            index = (index + 1) % self.board.len();
+            if index != opponent_store {
+                self.board[index] += 1;
+                seeds -= 1;
+            }
+        }
+        debug_assert_eq!(index, last);
+
+        let store = self.store(self.player);
+        let own_pits = self.pit(self.player, 0)..self.pit(self.player, self.pits);
+
+        // the last seed captures the opposite pit if it lands in an empty pit
+        if own_pits.contains(&last) && self.board[last] == 1 {
+            let opposite = 2 * self.pits - last;
+            if self.board[opposite] > 0 {
+                self.board[store] += 1 + std::mem::take(&mut self.board[opposite]);
+                self.board[last] = 0;
+            }
+        }
+
+        // once a side is empty, the other player keeps the rest of their seeds
+        if self
+            .side(PartizanPlayer::Left)
+            .iter()
+            .all(|&seeds| seeds == 0)
+            || self
+                .side(PartizanPlayer::Right)
+                .iter()
+                .all(|&seeds| seeds == 0)
+        {
+            for player in PartizanPlayer::all() {
+                for pit in 0..self.pits {
+                    let index = self.pit(player, pit);
+                    let seeds = std::mem::take(&mut self.board[index]);
+                    let store = self.store(player);
+                    self.board[store] += seeds;
+                }
+            }
+        }
+
+        self.move_count += 1;
+        if last != store {
+            self.player = self.player.next();
+        }
+
+        Ok(())
+    }
+
+    /// Landing the last seed in the player's store gives them another move.
+    fn same_player_after(&self, m: &Self::Move) -> bool {
+        self.last_seed(m) == self.store(self.player)
+    }
+
+    fn possible_moves(&self) -> Self::Iter<'_> {
+        self.side(self.player)
+            .iter()
+            .enumerate()
+            .filter(|(_, &seeds)| seeds > 0)
+            .map(|(pit, _)| KalahMove(pit))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    /// Extra moves are tried first.
+    fn move_order_key(&self, m: &Self::Move) -> i32 {
+        self.same_player_after(m) as i32
+    }
+
+    fn state(&self) -> GameState<Self::Player> {
+        if self.side(self.player).iter().any(|&seeds| seeds > 0) {
+            return GameState::Playable;
+        }
+
+        let (left, right) = self.stores();
+        match left.cmp(&right) {
+            std::cmp::Ordering::Greater => GameState::Win(PartizanPlayer::Left),
+            std::cmp::Ordering::Less => GameState::Win(PartizanPlayer::Right),
+            std::cmp::Ordering::Equal => GameState::Tie,
+        }
+    }
+
+    fn player(&self) -> Self::Player {
+        self.player
+    }
+
+    const HAS_HEURISTIC: bool = true;
+
+    /// The difference in stored seeds, from the perspective of the current player.
+    fn heuristic(&self) -> isize {
+        let (left, right) = self.stores();
+        let difference = left as isize - right as isize;
+
+        match self.player {
+            PartizanPlayer::Left => difference,
+            PartizanPlayer::Right => -difference,
+        }
+    }
+
+    /// Kalah is won by seeds, so larger wins are preferred.
+    const HAS_TERMINAL_VALUE: bool = true;
+
+    fn terminal_value(&self) -> Option<isize> {
+        Some(self.heuristic())
+    }
+}
+
+impl Display for Kalah {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Current player: {:?}", self.player)?;
+
+        let row = |seeds: Vec<usize>| {
+            seeds
+                .iter()
+                .map(|seeds| format!("{seeds:>3}"))
+                .collect::<Vec<_>>()
+                .concat()
+        };
+        let (left, right) = self.stores();
+
+        // Right's pits are shown from Right's side of the board, across from Left's
+        writeln!(
+            f,
+            "   {}",
+            row(self
+                .side(PartizanPlayer::Right)
+                .iter()
+                .rev()
+                .copied()
+                .collect())
+        )?;
+        writeln!(f, "{right:>3}{}{left:>3}", " ".repeat(3 * self.pits))?;
+        writeln!(f, "   {}", row(self.side(PartizanPlayer::Left).to_vec()))
+    }
+}
+
+impl Debug for Kalah {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        <Self as Display>::fmt(self, f)
+    }
+}
+
+/// Analyzes Kalah.
+///
+#[doc = include_str!("./README.md")]
+#[derive(Args, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct KalahArgs {
+    /// The amount of pits each player has
+    #[arg(long, default_value_t = 4)]
+    pits: usize,
+    /// The amount of seeds every pit starts with
+    #[arg(long, default_value_t = 3)]
+    seeds: usize,
+    /// Kalah moves, as the pits to sow from: p1 p2 ...
+    #[arg(value_parser = clap::value_parser!(KalahMove))]
+    moves: Vec<KalahMove>,
+}
+
+impl Default for KalahArgs {
+    fn default() -> Self {
+        Self {
+            pits: 4,
+            seeds: 3,
+            moves: vec![],
+        }
+    }
+}
+
+impl TryFrom<KalahArgs> for Kalah {
+    type Error = Error;
+
+    fn try_from(args: KalahArgs) -> Result<Self, Self::Error> {
+        if args.pits == 0 {
+            return Err(anyhow!("players need at least one pit."));
+        }
+
+        let mut game = Kalah::new(args.pits, args.seeds);
+
+        // parse every move in args, e.g. 0 1 in args
+        for game_move in args.moves {
+            move_failable(&mut game, &game_move)?;
+        }
+
+        Ok(game)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use game_solver::{move_scores, principal_variation, solve, GameSolveError, SolveMode};
+
+    use super::*;
+
+    /// Plays every move in `moves` in order.
+    fn played(mut game: Kalah, moves: &[usize]) -> Kalah {
+        for &m in moves {
+            game.make_move(&KalahMove(m)).unwrap();
+        }
+        game
+    }
+
+    /// The best margin of seeds the player to move can win by, found without the solver.
+    fn best_margin(game: &Kalah) -> isize {
+        match game.state() {
+            GameState::Playable => (),
+            GameState::Tie => return 0,
+            _ => return game.terminal_value().unwrap(),
+        }
+
+        game.possible_moves()
+            .map(|m| {
+                let mut next = game.clone();
+                next.make_move(&m).unwrap();
+                if game.same_player_after(&m) {
+                    best_margin(&next)
+                } else {
+                    -best_margin(&next)
+                }
+            })
+            .max()
+            .unwrap()
+    }
+
+    #[test]
+    fn sowing_and_extra_moves() {
+        let game = Kalah::new(4, 3);
+
+        // pit 1 sows into pits 2 and 3, then the store
+        assert!(game.same_player_after(&KalahMove(1)));
+        assert!(!game.same_player_after(&KalahMove(0)));
+
+        let game = played(game, &[1]);
+        assert_eq!(game.board, vec![3, 0, 4, 4, 1, 3, 3, 3, 3, 0]);
+        assert_eq!(game.player(), PartizanPlayer::Left);
+        assert_eq!(game.move_count(), 1);
+
+        // pit 3 sows into the store and Right's first three pits
+        let game = played(game, &[3]);
+        assert_eq!(game.board, vec![3, 0, 4, 0, 2, 4, 4, 4, 3, 0]);
+        assert_eq!(game.player(), PartizanPlayer::Right);
+
+        assert!(game.clone().make_move(&KalahMove(4)).is_err());
+        assert!(played(game, &[3]).make_move(&KalahMove(3)).is_err());
+    }
+
+    #[test]
+    fn sowing_skips_the_opponent_store() {
+        let mut game = Kalah::new(2, 0);
+        game.board = vec![0, 5, 0, 1, 1, 0];
+
+        // 5 seeds go around the board once, skipping Right's store, and end up in the emptied pit,
+        // which captures the seeds across from it
+        assert!(!game.same_player_after(&KalahMove(1)));
+        game.make_move(&KalahMove(1)).unwrap();
+        assert_eq!(game.board, vec![1, 0, 4, 0, 2, 0]);
+
+        // with one more seed, the last one lands in Left's store instead
+        let mut game = Kalah::new(2, 0);
+        game.board = vec![0, 6, 0, 1, 1, 0];
+        assert!(game.same_player_after(&KalahMove(1)));
+        game.make_move(&KalahMove(1)).unwrap();
+        assert_eq!(game.board, vec![1, 1, 2, 2, 2, 0]);
+        assert_eq!(game.player(), PartizanPlayer::Left);
+    }
+
+    #[test]
+    fn captures() {
+        let mut game = Kalah::new(3, 0);
+        game.board = vec![1, 0, 1, 0, 2, 3, 1, 0];
+
+        // the seed in pit 0 lands in the empty pit 1, capturing the 3 seeds across from it
+        game.make_move(&KalahMove(0)).unwrap();
+        assert_eq!(game.board, vec![0, 0, 1, 4, 2, 0, 1, 0]);
+        assert_eq!(game.player(), PartizanPlayer::Right);
+
+        // nothing is captured across from an empty pit
+        let mut game = Kalah::new(3, 0);
+        game.board = vec![1, 0, 1, 0, 2, 0, 1, 0];
+        game.make_move(&KalahMove(0)).unwrap();
+        assert_eq!(game.board, vec![0, 1, 1, 0, 2, 0, 1, 0]);
+    }
+
+    #[test]
+    fn empty_sides_end_the_game() {
+        let mut game = Kalah::new(2, 0);
+        game.board = vec![0, 1, 3, 2, 1, 2];
+
+        // Left's last seed goes into their store, which leaves their side empty
+        game.make_move(&KalahMove(1)).unwrap();
+        assert_eq!(game.board, vec![0, 0, 4, 0, 0, 5]);
+        assert_eq!(game.possible_moves().len(), 0);
+        assert_eq!(game.state(), GameState::Win(PartizanPlayer::Right));
+        assert_eq!(game.terminal_value(), Some(-1));
+    }
+
+    #[test]
+    fn solves_by_margin() {
+        for (pits, seeds) in [(1, 1), (2, 1), (2, 2), (3, 1), (3, 2)] {
+            let game = Kalah::new(pits, seeds);
+            assert_eq!(
+                solve(&game, &mut HashMap::new(), SolveMode::Strong, None).unwrap(),
+                best_margin(&game),
+                "Kalah({pits}, {seeds})"
+            );
+        }
+    }
+
+    #[test]
+    fn scores_extra_moves_from_the_same_player() {
+        let game = Kalah::new(3, 2);
+        let margin = best_margin(&game);
+
+        let scores = move_scores(&game, &mut HashMap::new(), None)
+            .unwrap()
+            .collect::<Result<Vec<_>, GameSolveError<Kalah>>>()
+            .unwrap();
+        assert_eq!(scores.iter().map(|(_, score)| *score).max(), Some(margin));
+
+        // the principal variation keeps the same player moving after extra moves
+        let variation = principal_variation(&game, &mut HashMap::new());
+        let end = variation.iter().fold(game.clone(), |mut game, m| {
+            game.make_move(m).unwrap();
+            game
+        });
+        assert!(end.state() != GameState::Playable);
+
+        let (left, right) = end.stores();
+        assert_eq!(left as isize - right as isize, margin);
+    }
+
+    #[test]
+    fn args() {
+        let game = Kalah::try_from(KalahArgs {
+            pits: 4,
+            seeds: 3,
+            moves: vec![KalahMove(1), KalahMove(3)],
+        })
+        .unwrap();
+        assert_eq!(game.player(), PartizanPlayer::Right);
+        assert_eq!(game.stores(), (2, 0));
+
+        assert!(Kalah::try_from(KalahArgs {
+            pits: 0,
+            seeds: 3,
+            moves: vec![],
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn moves_round_trip() {
+        crate::util::assert_moves_round_trip(&Kalah::new(3, 2), 3);
+    }
+}
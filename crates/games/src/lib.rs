@@ -1,18 +1,22 @@
 pub mod util;
 
 pub mod chomp;
+pub mod connect_four;
 pub mod domineering;
+pub mod kalah;
 pub mod naive_nim;
+pub mod notakto;
 pub mod order_and_chaos;
 pub mod reversi;
 pub mod sprouts;
 pub mod tic_tac_toe;
+pub mod wythoff;
 pub mod zener;
 
 use crate::{
-    chomp::ChompArgs, domineering::DomineeringArgs, naive_nim::NimArgs,
-    order_and_chaos::OrderAndChaosArgs, reversi::ReversiArgs, sprouts::SproutsArgs,
-    tic_tac_toe::TicTacToeArgs,
+    chomp::ChompArgs, connect_four::ConnectFourArgs, domineering::DomineeringArgs,
+    kalah::KalahArgs, naive_nim::NimArgs, notakto::NotaktoArgs, order_and_chaos::OrderAndChaosArgs,
+    reversi::ReversiArgs, sprouts::SproutsArgs, tic_tac_toe::TicTacToeArgs, wythoff::WythoffArgs,
 };
 use clap::Subcommand;
 use once_cell::sync::Lazy;
@@ -29,9 +33,13 @@ pub enum Games {
     Chomp(ChompArgs),
     Sprouts(SproutsArgs),
     Zener(ZenerArgs),
+    ConnectFour(ConnectFourArgs),
+    Notakto(NotaktoArgs),
+    Kalah(KalahArgs),
+    Wythoff(WythoffArgs),
 }
 
-pub static DEFAULT_GAMES: Lazy<[Games; 8]> = Lazy::new(|| {
+pub static DEFAULT_GAMES: Lazy<[Games; 12]> = Lazy::new(|| {
     [
         Games::Reversi(Default::default()),
         Games::TicTacToe(Default::default()),
@@ -41,6 +49,10 @@ pub static DEFAULT_GAMES: Lazy<[Games; 8]> = Lazy::new(|| {
         Games::Chomp(Default::default()),
         Games::Sprouts(Default::default()),
         Games::Zener(Default::default()),
+        Games::ConnectFour(Default::default()),
+        Games::Notakto(Default::default()),
+        Games::Kalah(Default::default()),
+        Games::Wythoff(Default::default()),
     ]
 });
 
@@ -55,6 +67,10 @@ impl Games {
             Self::Chomp(_) => "Chomp".to_string(),
             Self::Sprouts(_) => "Sprouts".to_string(),
             Self::Zener(_) => "Zener".to_string(),
+            Self::ConnectFour(_) => "Connect Four".to_string(),
+            Self::Notakto(_) => "Notakto".to_string(),
+            Self::Kalah(_) => "Kalah".to_string(),
+            Self::Wythoff(_) => "Wythoff's Game".to_string(),
         }
     }
 
@@ -68,6 +84,10 @@ impl Games {
             Self::Chomp(_) => include_str!("./chomp/README.md"),
             Self::Sprouts(_) => include_str!("./sprouts/README.md"),
             Self::Zener(_) => include_str!("./zener/README.md"),
+            Self::ConnectFour(_) => include_str!("./connect_four/README.md"),
+            Self::Notakto(_) => include_str!("./notakto/README.md"),
+            Self::Kalah(_) => include_str!("./kalah/README.md"),
+            Self::Wythoff(_) => include_str!("./wythoff/README.md"),
         }
     }
 
@@ -123,6 +143,128 @@ impl Games {
                 &mut cache,
                 "crates/games/src/zener/README.md"
             ),
+            Self::ConnectFour(_) => egui_commonmark::commonmark_str!(
+                "connect_four",
+                ui,
+                &mut cache,
+                "crates/games/src/connect_four/README.md"
+            ),
+            Self::Notakto(_) => egui_commonmark::commonmark_str!(
+                "notakto",
+                ui,
+                &mut cache,
+                "crates/games/src/notakto/README.md"
+            ),
+            Self::Kalah(_) => egui_commonmark::commonmark_str!(
+                "kalah",
+                ui,
+                &mut cache,
+                "crates/games/src/kalah/README.md"
+            ),
+            Self::Wythoff(_) => egui_commonmark::commonmark_str!(
+                "wythoff",
+                ui,
+                &mut cache,
+                "crates/games/src/wythoff/README.md"
+            ),
         };
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, fmt::Debug, hash::Hash};
+
+    use game_solver::{
+        game::Game, par_solve, player::TwoPlayer, solve, solve_mtdf, solve_with_aspiration,
+        SolveMode,
+    };
+    use ndarray::IntoDimension;
+
+    use crate::{
+        chomp::Chomp,
+        connect_four::ConnectFour,
+        domineering::Domineering,
+        kalah::Kalah,
+        naive_nim::Nim,
+        notakto::Notakto,
+        order_and_chaos::OrderAndChaos,
+        reversi::Reversi,
+        sprouts::Sprouts,
+        tic_tac_toe::{TicTacToe, TicTacToeArgs, TicTacToeMove},
+        wythoff::Wythoff,
+    };
+
+    /// Checks that MTD(f) and aspiration windows find the same score as `solve`,
+    /// no matter the guess, and that a weak solve finds the same outcome.
+    fn assert_searches_match<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash + Debug>(
+        game: T,
+    ) {
+        let score = solve(&game, &mut HashMap::new(), SolveMode::Strong, None).unwrap();
+
+        assert_eq!(
+            solve(&game, &mut HashMap::new(), SolveMode::Weak, None).unwrap(),
+            score.signum(),
+            "weak solve of {game:?}"
+        );
+
+        for guess in [0, score, score - 3, score + 3] {
+            assert_eq!(
+                solve_mtdf(&game, &mut HashMap::new(), guess, None, &None).unwrap(),
+                score,
+                "MTD(f) on {game:?} with first guess {guess}"
+            );
+
+            for delta in [1, 4] {
+                assert_eq!(
+                    solve_with_aspiration(&game, &mut HashMap::new(), guess, delta, None, &None)
+                        .unwrap(),
+                    score,
+                    "aspiration window on {game:?} with guess {guess} and delta {delta}"
+                );
+            }
+        }
+    }
+
+    // Zener is left out, as its board is too large to solve here
+    #[test]
+    fn searches_match_solve() {
+        assert_searches_match(Chomp::new(3, 3));
+        assert_searches_match(ConnectFour::new(3, 3, 3).unwrap());
+        assert_searches_match(Domineering::<4, 4>::new());
+        assert_searches_match(Kalah::new(3, 2));
+        assert_searches_match(Nim::new(vec![2, 3]));
+        assert_searches_match(Nim::new_misere(vec![1, 2, 2]));
+
+        let mut notakto = Notakto::new(2, 3, 3);
+        notakto
+            .make_move(&TicTacToeMove(vec![1, 1].into_dimension()))
+            .unwrap();
+        assert_searches_match(notakto);
+
+        assert_searches_match(OrderAndChaos::<3, 3, 3, 3>::new());
+        assert_searches_match(Reversi::new(4, 4));
+        assert_searches_match(Sprouts::new(3));
+        assert_searches_match(TicTacToe::try_from(TicTacToeArgs::default()).unwrap());
+        assert_searches_match(Wythoff::new(3, 4));
+    }
+
+    #[test]
+    fn par_solve_matches_solve() {
+        let domineering = Domineering::<5, 5>::new();
+        assert_eq!(
+            par_solve(&domineering, 4, None, &None).unwrap(),
+            solve(&domineering, &mut HashMap::new(), SolveMode::Strong, None).unwrap()
+        );
+
+        let chomp = Chomp::new(4, 5);
+        assert_eq!(
+            par_solve(&chomp, 4, None, &None).unwrap(),
+            solve(&chomp, &mut HashMap::new(), SolveMode::Strong, None).unwrap()
+        );
+
+        // a single thread is the same as solving normally
+        let tic_tac_toe = TicTacToe::try_from(TicTacToeArgs::default()).unwrap();
+        assert_eq!(par_solve(&tic_tac_toe, 1, None, &None).unwrap(), 0);
+    }
+}
@@ -5,7 +5,7 @@ pub mod gui;
 use anyhow::Error;
 use clap::Args;
 use game_solver::{
-    game::{Game, GameState, Normal, NormalImpartial},
+    game::{Game, GameState, Normal, NormalImpartial, StateType},
     player::ImpartialPlayer,
 };
 use serde::{Deserialize, Serialize};
@@ -15,13 +15,17 @@ use std::{
 };
 use thiserror::Error;
 
+use nimnim::Nimber;
+
 use crate::util::{move_failable, move_natural::NaturalMove};
 
-#[derive(Clone, Hash, Eq, PartialEq)]
+#[derive(Clone, Hash, Eq, PartialEq, Serialize, Deserialize)]
 pub struct Nim {
     heaps: Vec<usize>,
     move_count: usize,
     max_moves: usize,
+    /// Whether the last player to take an object loses, instead of winning.
+    misere: bool,
 }
 
 type NimMove = NaturalMove<2>;
@@ -35,8 +39,35 @@ impl Nim {
             move_count: 0,
             // sum of all the heaps is the upper bound for the amount of moves
             max_moves: heaps.iter().sum::<usize>(),
+            misere: false,
+        }
+    }
+
+    /// Create a new game of misère Nim with the given heaps,
+    /// where the player who takes the last object loses.
+    pub fn new_misere(heaps: Vec<usize>) -> Self {
+        Self {
+            misere: true,
+            ..Self::new(heaps)
         }
     }
+
+    /// The Sprague-Grundy value of this position under normal play,
+    /// which is the nim-sum of its heaps - no search required.
+    pub fn grundy(&self) -> Nimber {
+        self.heaps.iter().fold(Nimber(0), |a, &h| a + Nimber(h))
+    }
+
+    /// Whether the player to move loses with perfect play, computed without search.
+    pub fn is_losing_for_next(&self) -> bool {
+        // misère nim only plays differently once every heap has at most one object,
+        // where the player to move loses if there's an odd amount of objects left
+        if self.misere && self.heaps.iter().all(|&heap| heap <= 1) {
+            return self.heaps.iter().sum::<usize>() % 2 == 1;
+        }
+
+        self.grundy() == Nimber(0)
+    }
 }
 
 #[derive(Error, Debug, Clone)]
@@ -60,6 +91,7 @@ impl Game for Nim {
 
     /// Define Nim as a zero-sum impartial game
     type Player = ImpartialPlayer;
+    type Key = Self;
     type MoveError = NimMoveError;
 
     fn max_moves(&self) -> Option<usize> {
@@ -94,6 +126,22 @@ impl Game for Nim {
         Ok(())
     }
 
+    const SUPPORTS_UNDO: bool = true;
+
+    fn undo_move(&mut self, m: &Self::Move) -> Result<(), Self::MoveError> {
+        let [heap, amount] = m.0;
+        if heap >= self.heaps.len() {
+            return Err(NimMoveError::HeapOutOfBounds {
+                heap,
+                heap_count: self.heaps.len(),
+            });
+        }
+
+        self.heaps[heap] += amount;
+        self.move_count -= 1;
+        Ok(())
+    }
+
     fn possible_moves(&self) -> Self::Iter<'_> {
         let mut moves = Vec::new();
 
@@ -107,13 +155,55 @@ impl Game for Nim {
         moves.into_iter()
     }
 
+    fn describe_move(&self, m: &Self::Move) -> String {
+        let [heap, amount] = m.0;
+
+        if self.heaps.get(heap) == Some(&amount) && amount > 1 {
+            format!("take all {amount} objects from heap {heap}")
+        } else if amount == 1 {
+            format!("take 1 object from heap {heap}")
+        } else {
+            format!("take {amount} objects from heap {heap}")
+        }
+    }
+
+    /// The game only ends once every heap is empty,
+    /// so the only move that can end it takes the last heap that isn't.
+    fn find_immediately_resolvable_game(&self) -> Result<Option<Self>, Self::MoveError> {
+        let mut heaps = self.heaps.iter().enumerate().filter(|(_, &heap)| heap > 0);
+
+        match (heaps.next(), heaps.next()) {
+            (Some((heap, &amount)), None) => {
+                let mut game = self.clone();
+                game.make_move(&NaturalMove([heap, amount]))?;
+                Ok(Some(game))
+            }
+            _ => Ok(None),
+        }
+    }
+
     fn state(&self) -> GameState<Self::Player> {
-        <Self as Normal>::state(&self)
+        if self.misere {
+            StateType::Misere.state(self)
+        } else {
+            <Self as Normal>::state(self)
+        }
     }
 
     fn player(&self) -> Self::Player {
         ImpartialPlayer::Next
     }
+
+    /// The order of the heaps doesn't change how the game plays out, so the heaps are sorted.
+    fn canonical_key(&self) -> Option<Self> {
+        let mut heaps = self.heaps.clone();
+        heaps.sort_unstable();
+
+        Some(Self {
+            heaps,
+            ..self.clone()
+        })
+    }
 }
 
 impl Display for Nim {
@@ -144,6 +234,9 @@ pub struct NimArgs {
     /// Nim moves, ordered as x1-y1 x2-y2 ...
     #[arg(value_parser = clap::value_parser!(NimMove))]
     moves: Vec<NimMove>,
+    /// Play misère Nim, where the player who takes the last object loses.
+    #[arg(long)]
+    misere: bool,
 }
 
 impl Default for NimArgs {
@@ -151,6 +244,7 @@ impl Default for NimArgs {
         Self {
             configuration: "3,5,7".to_string(),
             moves: vec![],
+            misere: false,
         }
     }
 }
@@ -168,7 +262,11 @@ impl TryFrom<NimArgs> for Nim {
             .collect::<Vec<_>>();
 
         // create a new game of Nim with the given configuration
-        let mut game = Nim::new(config);
+        let mut game = if args.misere {
+            Nim::new_misere(config)
+        } else {
+            Nim::new(config)
+        };
 
         // parse every move in args, e.g. 0-0 1-1 in args
         for nim_move in args.moves {
@@ -181,17 +279,38 @@ impl TryFrom<NimArgs> for Nim {
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
-
-    use game_solver::{move_scores, CollectedMoves};
+    use std::{
+        collections::HashMap,
+        num::NonZeroU64,
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use game_solver::{
+        disjoint_game::DisjointImpartialNormalGameVec,
+        grundy_equivalent, grundy_value, move_scores, solve, solve_best_move, solve_counted,
+        solve_depth_limited, solve_iterative_deepening, solve_with_timeout, split_grundy_value,
+        stats::Stats,
+        transposition::{load_from, save_to, Score},
+        CollectedMoves, GameSolveError, SolveMode,
+    };
     use itertools::Itertools;
 
-    use crate::util::move_score::best_move_score_testing;
+    use crate::{chomp::Chomp, util::move_score::best_move_score_testing, wythoff::Wythoff};
 
     use super::*;
 
     fn play(nim: Nim) -> CollectedMoves<Nim> {
-        move_scores(&nim, &mut HashMap::new(), None).collect_vec()
+        move_scores(&nim, &mut HashMap::new(), None)
+            .unwrap()
+            .collect_vec()
+    }
+
+    fn score(nim: &Nim) -> isize {
+        solve(nim, &mut HashMap::new(), SolveMode::Strong, None).unwrap()
     }
 
     #[test]
@@ -212,9 +331,169 @@ mod tests {
 
     #[test]
     fn empty_heap() {
-        // unless the heaps have nothing, in which we cant play
-        assert!(play(Nim::new(vec![0])).is_empty());
-        assert!(play(Nim::new(vec![0, 0])).is_empty());
+        // unless the heaps have nothing, in which case the game is already over
+        for heaps in [vec![0], vec![0, 0]] {
+            assert!(matches!(
+                move_scores(&Nim::new(heaps), &mut HashMap::new(), None),
+                Err(GameSolveError::GameOver(GameState::Win(
+                    ImpartialPlayer::Previous
+                )))
+            ));
+        }
+        assert!(
+            solve_best_move(&Nim::new(vec![0]), &mut HashMap::new(), None)
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn depth_limited_without_heuristic_is_exact() {
+        // nim doesn't have a heuristic, so depth-limited solving should solve exactly
+        let game = Nim::new(vec![3, 4]);
+        assert_eq!(
+            solve_depth_limited(&game, &mut HashMap::new(), 1, None, &None).unwrap(),
+            solve(&game, &mut HashMap::new(), SolveMode::Strong, None).unwrap()
+        );
+    }
+
+    #[test]
+    fn iterative_deepening_without_heuristic_is_exact() {
+        let game = Nim::new(vec![2, 3]);
+        let iterations =
+            solve_iterative_deepening(&game, &mut HashMap::new(), None, &None).collect_vec();
+
+        assert_eq!(
+            iterations,
+            vec![(
+                5,
+                solve(&game, &mut HashMap::new(), SolveMode::Strong, None).unwrap()
+            )]
+        );
+    }
+
+    #[test]
+    fn timeout_finishes_small_games() {
+        let game = Nim::new(vec![2, 3]);
+        assert_eq!(
+            solve_with_timeout(&game, &mut HashMap::new(), Duration::from_secs(60), None).unwrap(),
+            Some(solve(&game, &mut HashMap::new(), SolveMode::Strong, None).unwrap())
+        );
+    }
+
+    #[test]
+    fn transposition_table_round_trip() {
+        let game = Nim::new(vec![2, 3, 4]);
+        let mut transposition_table = HashMap::new();
+        let score = solve(&game, &mut transposition_table, SolveMode::Strong, None).unwrap();
+
+        let mut saved = Vec::new();
+        save_to(&transposition_table, &mut saved).unwrap();
+        let mut loaded: HashMap<Nim, Score> = load_from(saved.as_slice()).unwrap();
+
+        assert_eq!(loaded, transposition_table);
+        assert_eq!(
+            solve(&game, &mut loaded, SolveMode::Strong, None).unwrap(),
+            score
+        );
+    }
+
+    #[test]
+    fn best_move_takes_heap() {
+        // taking the entire heap is the fastest win
+        assert_eq!(
+            solve_best_move(&Nim::new(vec![5]), &mut HashMap::new(), None).unwrap(),
+            Some((NaturalMove([0, 5]), 5))
+        );
+    }
+
+    #[test]
+    fn misere_single_objects() {
+        // an odd amount of single objects is a win in normal play,
+        // but a loss in misère play, since the last object is taken by the first player.
+        assert_eq!(score(&Nim::new(vec![1, 1, 1])), 1);
+        assert_eq!(score(&Nim::new_misere(vec![1, 1, 1])), -1);
+
+        // and vice versa for an even amount
+        assert!(score(&Nim::new(vec![1, 1])) < 0);
+        assert!(score(&Nim::new_misere(vec![1, 1])) > 0);
+    }
+
+    #[test]
+    fn misere_matches_normal_with_large_heaps() {
+        // with a heap larger than one, misère Nim is won by the same player as normal Nim
+        for heaps in [vec![2, 2], vec![2, 3], vec![1, 2, 3], vec![3, 1, 1]] {
+            let normal = score(&Nim::new(heaps.clone()));
+            let misere = score(&Nim::new_misere(heaps));
+            assert_eq!(normal > 0, misere > 0);
+        }
+    }
+
+    #[test]
+    fn terminal_ends_are_attributed() {
+        // the first player takes the only object and wins
+        let nim = Nim::new(vec![1]);
+        let stats = Stats::new(nim.player(), nim.move_count());
+        solve(&nim, &mut HashMap::new(), SolveMode::Strong, Some(&stats)).unwrap();
+        assert!(stats.terminal_ends.winning.load(Ordering::Relaxed) > 0);
+        assert_eq!(stats.terminal_ends.losing.load(Ordering::Relaxed), 0);
+
+        // the second player always takes the last object
+        let nim = Nim::new(vec![1, 1]);
+        let stats = Stats::new(nim.player(), nim.move_count());
+        solve(&nim, &mut HashMap::new(), SolveMode::Strong, Some(&stats)).unwrap();
+        assert_eq!(stats.terminal_ends.winning.load(Ordering::Relaxed), 0);
+        assert!(stats.terminal_ends.losing.load(Ordering::Relaxed) > 0);
+    }
+
+    #[test]
+    fn successors_match_moves() {
+        let nim = Nim::new(vec![3, 4, 5]);
+        let successors = nim.successors().collect::<Result<Vec<_>, _>>().unwrap();
+
+        assert_eq!(successors.len(), nim.possible_moves().count());
+        for (m, successor) in nim.possible_moves().zip(&successors) {
+            let mut board = nim.clone();
+            board.make_move(&m).unwrap();
+            assert_eq!(&board, successor);
+        }
+    }
+
+    #[test]
+    fn grundy_value_of_single_heap() {
+        for n in 0..8 {
+            assert_eq!(
+                grundy_value(&Nim::new(vec![n]), &mut HashMap::new()).unwrap(),
+                Nimber(n)
+            );
+        }
+    }
+
+    #[test]
+    fn grundy_value_is_nim_sum() {
+        let mut transposition_table = HashMap::new();
+        assert_eq!(
+            grundy_value(&Nim::new(vec![3, 5]), &mut transposition_table).unwrap(),
+            Nimber(3) + Nimber(5)
+        );
+        assert_eq!(
+            grundy_value(&Nim::new(vec![1, 2, 3]), &mut transposition_table).unwrap(),
+            Nimber(0)
+        );
+    }
+
+    #[test]
+    fn misere_has_no_grundy_value() {
+        for game in [Nim::new_misere(vec![1, 2]), Nim::new_misere(vec![])] {
+            assert!(matches!(
+                grundy_value(&game, &mut HashMap::new()),
+                Err(GameSolveError::NotNormalPlay)
+            ));
+            assert!(matches!(
+                split_grundy_value(&game, &mut HashMap::new()),
+                Err(GameSolveError::NotNormalPlay)
+            ));
+        }
     }
 
     #[test]
@@ -230,4 +509,212 @@ mod tests {
         );
         assert_eq!(best_move_score_testing(play(Nim::new(vec![7, 7]))).1, -1);
     }
+
+    #[test]
+    fn disjoint_heaps_match_nim() {
+        let sum = DisjointImpartialNormalGameVec::new(vec![
+            Nim::new(vec![1]),
+            Nim::new(vec![2]),
+            Nim::new(vec![3]),
+        ]);
+
+        assert_eq!(sum.possible_moves().count(), 1 + 2 + 3);
+        assert_eq!(sum.max_moves(), Some(1 + 2 + 3));
+        assert_eq!(sum.split().unwrap().len(), 3);
+        assert_eq!(
+            grundy_value(&sum, &mut HashMap::new()).unwrap(),
+            grundy_value(&Nim::new(vec![1, 2, 3]), &mut HashMap::new()).unwrap()
+        );
+
+        let sum = DisjointImpartialNormalGameVec::new(vec![Nim::new(vec![3]), Nim::new(vec![5])]);
+        assert_eq!(
+            grundy_value(&sum, &mut HashMap::new()).unwrap(),
+            Nimber(3) + Nimber(5)
+        );
+    }
+
+    #[test]
+    fn grundy_equivalent_positions() {
+        let heap = Nim::new(vec![3]);
+
+        // 1 and 2 nim-sum to 3
+        assert!(grundy_equivalent(&heap, &Nim::new(vec![1, 2])));
+        // a single Wythoff heap can't be taken from together with an empty one
+        assert!(grundy_equivalent(&heap, &Wythoff::new(0, 3)));
+        // every square but the poisoned one is a heap of its own
+        assert!(grundy_equivalent(&heap, &Chomp::new(4, 1)));
+
+        assert!(!grundy_equivalent(&heap, &Nim::new(vec![2])));
+        // taking one from both heaps wins right away, unlike in Nim
+        assert!(!grundy_equivalent(
+            &Nim::new(vec![1, 1]),
+            &Wythoff::new(1, 1)
+        ));
+    }
+
+    #[test]
+    fn grundy_matches_search() {
+        for heaps in (0..3).map(|_| 0..4).multi_cartesian_product() {
+            for game in [Nim::new(heaps.clone()), Nim::new_misere(heaps.clone())] {
+                let next_wins = score(&game) > 0;
+
+                assert_eq!(game.is_losing_for_next(), !next_wins, "{heaps:?}");
+            }
+
+            let game = Nim::new(heaps);
+            assert_eq!(
+                game.grundy(),
+                grundy_value(&game, &mut HashMap::new()).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn grundy_of_large_heaps() {
+        let game = Nim::new(vec![1 << 40, 1 << 40, 12345]);
+
+        assert_eq!(game.grundy(), Nimber(12345));
+        assert!(!game.is_losing_for_next());
+        assert!(Nim::new(vec![1 << 40, 1 << 40]).is_losing_for_next());
+    }
+
+    #[test]
+    fn max_depth_grows_with_heaps() {
+        let max_depth = |heaps: Vec<usize>| {
+            let game = Nim::new(heaps);
+            let stats = Stats::new(game.player(), game.move_count());
+            solve(&game, &mut HashMap::new(), SolveMode::Strong, Some(&stats)).unwrap();
+            stats.max_depth.load(Ordering::Relaxed)
+        };
+
+        // when taking every object is the only way to win,
+        // every object but the last one (which is an immediate win) is searched
+        assert_eq!(max_depth(vec![1, 1]), 1);
+        assert_eq!(max_depth(vec![2, 2]), 3);
+        assert_eq!(max_depth(vec![4, 4]), 7);
+    }
+
+    #[test]
+    fn counted_states_match_stats() {
+        let game = Nim::new(vec![3, 4, 5]);
+        let stats = Stats::new(game.player(), game.move_count());
+        let score = solve(&game, &mut HashMap::new(), SolveMode::Strong, Some(&stats)).unwrap();
+
+        let (counted_score, states) = solve_counted(&game, &mut HashMap::new(), &None).unwrap();
+        assert_eq!(counted_score, score);
+        assert_eq!(states, stats.states_explored.load(Ordering::Relaxed));
+        assert!(states > 1);
+    }
+
+    #[test]
+    fn progress_is_reported() {
+        let game = Nim::new(vec![3, 4, 5]);
+        let reports = Arc::new(AtomicU64::new(0));
+        let stats = Stats::new(game.player(), game.move_count()).with_progress(
+            NonZeroU64::new(10).unwrap(),
+            {
+                let reports = reports.clone();
+                move |_| {
+                    reports.fetch_add(1, Ordering::Relaxed);
+                }
+            },
+        );
+
+        solve(&game, &mut HashMap::new(), SolveMode::Strong, Some(&stats)).unwrap();
+
+        assert!(reports.load(Ordering::Relaxed) > 0);
+        assert_eq!(
+            reports.load(Ordering::Relaxed),
+            stats.states_explored.load(Ordering::Relaxed) / 10
+        );
+    }
+
+    #[test]
+    fn moves_round_trip() {
+        crate::util::assert_moves_round_trip(&Nim::new(vec![3, 4, 5]), 2);
+    }
+
+    #[test]
+    fn describe_moves() {
+        let game = Nim::new(vec![3, 1]);
+
+        assert_eq!(
+            game.describe_move(&NaturalMove([0, 2])),
+            "take 2 objects from heap 0"
+        );
+        assert_eq!(
+            game.describe_move(&NaturalMove([0, 3])),
+            "take all 3 objects from heap 0"
+        );
+        assert_eq!(
+            game.describe_move(&NaturalMove([1, 1])),
+            "take 1 object from heap 1"
+        );
+    }
+
+    #[test]
+    fn permuted_heaps_share_entries() {
+        let hash = |game: &Nim| {
+            use std::hash::{DefaultHasher, Hasher};
+
+            let mut hasher = DefaultHasher::new();
+            game.key().hash(&mut hasher);
+            hasher.finish()
+        };
+
+        let game = Nim::new(vec![3, 5, 7]);
+        let permuted = Nim::new(vec![7, 3, 5]);
+        assert_eq!(game.key(), permuted.key());
+        assert_eq!(hash(&game), hash(&permuted));
+        assert_ne!(game.key(), Nim::new_misere(vec![3, 5, 7]).key());
+
+        let mut transposition_table = HashMap::new();
+        let first = Stats::new(game.player(), game.move_count());
+        let score = solve(
+            &game,
+            &mut transposition_table,
+            SolveMode::Strong,
+            Some(&first),
+        )
+        .unwrap();
+
+        let second = Stats::new(permuted.player(), permuted.move_count());
+        assert_eq!(
+            solve(
+                &permuted,
+                &mut transposition_table,
+                SolveMode::Strong,
+                Some(&second)
+            )
+            .unwrap(),
+            score
+        );
+
+        // the permuted heaps are already solved
+        assert!(
+            second.states_explored.load(Ordering::Relaxed)
+                < first.states_explored.load(Ordering::Relaxed) / 10
+        );
+    }
+
+    #[test]
+    fn resolvable_games_match() {
+        for game in [Nim::new(vec![3, 4, 5]), Nim::new_misere(vec![1, 2, 2])] {
+            crate::util::assert_resolvable_games_match(&game, 4);
+        }
+    }
+
+    #[test]
+    fn undo_restores_the_game() {
+        let game = Nim::new(vec![3, 4, 5]);
+
+        for m in game.possible_moves() {
+            let mut board = game.clone();
+            board.make_move(&m).unwrap();
+            board.undo_move(&m).unwrap();
+            assert_eq!(board, game);
+        }
+
+        assert!(game.clone().undo_move(&NaturalMove([3, 1])).is_err());
+    }
 }
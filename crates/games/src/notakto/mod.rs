@@ -0,0 +1,259 @@
+#![doc = include_str!("./README.md")]
+
+#[cfg(feature = "egui")]
+pub mod gui;
+use anyhow::Error;
+use clap::Args;
+use game_solver::{
+    game::{Game, GameState, Normal, NormalImpartial},
+    player::ImpartialPlayer,
+};
+use ndarray::{ArrayD, Dim, IntoDimension, IxDyn, IxDynImpl};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{Debug, Display, Formatter},
+    hash::Hash,
+    str::FromStr,
+};
+
+use crate::{
+    tic_tac_toe::{
+        check_win_length, offsets, winning_line, Square, TicTacToeMove, TicTacToeMoveError,
+    },
+    util::move_failable,
+};
+
+#[derive(Clone, Hash, Eq, PartialEq)]
+pub struct Notakto {
+    dim: usize,
+    size: usize,
+    /// The amount of squares in a row that loses the game
+    win_length: usize,
+    /// Every placed square is an X, as both players share it
+    board: ArrayD<Option<Square>>,
+    move_count: usize,
+}
+
+impl Notakto {
+    /// # Panics
+    ///
+    /// If `win_length` is zero or larger than `size`.
+    pub fn new(dim: usize, size: usize, win_length: usize) -> Self {
+        check_win_length(size, win_length).unwrap();
+
+        Self {
+            dim,
+            size,
+            win_length,
+            board: ArrayD::from_elem(IxDyn(&vec![size; dim]), None),
+            move_count: 0,
+        }
+    }
+
+    /// Whether there is a line of `win_length` squares going through `point`.
+    fn completes_line(&self, board: &ArrayD<Option<Square>>, point: &Dim<IxDynImpl>) -> bool {
+        offsets(point, board.shape())
+            .iter()
+            .any(|offset| winning_line(board, point, offset, self.win_length).is_some())
+    }
+
+    /// Whether a line has already been made on the board.
+    fn has_line(&self) -> bool {
+        self.board.indexed_iter().any(|(index, square)| {
+            square.is_some() && self.completes_line(&self.board, &index.into_dimension())
+        })
+    }
+}
+
+impl Normal for Notakto {}
+impl NormalImpartial for Notakto {}
+impl Game for Notakto {
+    type Move = TicTacToeMove;
+    type Iter<'a> = std::vec::IntoIter<Self::Move>;
+    type Player = ImpartialPlayer;
+    type Key = Self;
+    type MoveError = TicTacToeMoveError;
+
+    fn max_moves(&self) -> Option<usize> {
+        Some(self.size.pow(self.dim as u32))
+    }
+
+    fn move_count(&self) -> usize {
+        self.move_count
+    }
+
+    fn make_move(&mut self, m: &Self::Move) -> Result<(), Self::MoveError> {
+        if self.board.get(m.0.clone()).unwrap().is_none() {
+            *self.board.get_mut(m.0.clone()).unwrap() = Some(Square::X);
+            self.move_count += 1;
+            Ok(())
+        } else {
+            Err(TicTacToeMoveError::NonEmptySquare(m.clone()))
+        }
+    }
+
+    /// Only moves that don't complete a line are possible - completing one would lose anyway.
+    fn possible_moves(&self) -> Self::Iter<'_> {
+        let mut board = self.board.clone();
+
+        self.board
+            .indexed_iter()
+            .filter(|(_, square)| square.is_none())
+            .map(|(index, _)| index.into_dimension())
+            .filter(|point| {
+                board[point] = Some(Square::X);
+                let completes_line = self.completes_line(&board, point);
+                board[point] = None;
+                !completes_line
+            })
+            .map(TicTacToeMove)
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn state(&self) -> GameState<Self::Player> {
+        // a line can still be made through `make_move` - the player who made it loses
+        if self.has_line() {
+            return GameState::Win(self.player());
+        }
+
+        <Self as Normal>::state(self)
+    }
+
+    fn player(&self) -> Self::Player {
+        ImpartialPlayer::Next
+    }
+}
+
+impl Display for Notakto {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        for (index, square) in self.board.indexed_iter() {
+            writeln!(
+                f,
+                "{} @ {}",
+                if square.is_some() { 'X' } else { '-' },
+                TicTacToeMove(index)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Debug for Notakto {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        <Self as Display>::fmt(self, f)
+    }
+}
+
+/// Analyzes Notakto.
+///
+#[doc = include_str!("./README.md")]
+#[derive(Args, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct NotaktoArgs {
+    /// The amount of dimensions in the game.
+    dimensions: usize,
+    /// The size of the board in every dimension.
+    size: usize,
+    /// The amount of squares in a row that loses - defaults to the size of the board.
+    #[arg(long)]
+    win_length: Option<usize>,
+    /// The moves to make in the game, by dimension and index in that dimension.
+    moves: Vec<String>,
+}
+
+impl Default for NotaktoArgs {
+    fn default() -> Self {
+        Self {
+            dimensions: 2,
+            size: 3,
+            win_length: None,
+            moves: vec![],
+        }
+    }
+}
+
+impl TryFrom<NotaktoArgs> for Notakto {
+    type Error = Error;
+
+    fn try_from(value: NotaktoArgs) -> Result<Self, Self::Error> {
+        let win_length = value.win_length.unwrap_or(value.size);
+        check_win_length(value.size, win_length)?;
+
+        let mut game = Notakto::new(value.dimensions, value.size, win_length);
+
+        // parse every move in args, e.g. 0-0 1-1 in args
+        for arg in value.moves {
+            move_failable(&mut game, &TicTacToeMove::from_str(&arg)?)?;
+        }
+
+        Ok(game)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use game_solver::{grundy_value, solve, solve_best_move, SolveMode};
+    use nimnim::Nimber;
+
+    use super::*;
+
+    fn at(point: [usize; 2]) -> TicTacToeMove {
+        TicTacToeMove(point.to_vec().into_dimension())
+    }
+
+    #[test]
+    fn first_player_wins() {
+        // the first player wins a single board by taking the center
+        let game = Notakto::new(2, 3, 3);
+        assert!(solve(&game, &mut HashMap::new(), SolveMode::Strong, None).unwrap() > 0);
+
+        let (best_move, _) = solve_best_move(&game, &mut HashMap::new(), None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(best_move, at([1, 1]));
+
+        assert_ne!(grundy_value(&game, &mut HashMap::new()).unwrap(), Nimber(0));
+    }
+
+    #[test]
+    fn lines_are_never_offered() {
+        let mut game = Notakto::new(2, 3, 3);
+        game.make_move(&at([0, 0])).unwrap();
+        game.make_move(&at([0, 1])).unwrap();
+
+        assert!(!game.possible_moves().any(|m| m == at([0, 2])));
+        assert_eq!(game.possible_moves().len(), 6);
+    }
+
+    #[test]
+    fn making_a_line_loses() {
+        let mut game = Notakto::new(2, 3, 3);
+        game.make_move(&at([0, 0])).unwrap();
+        game.make_move(&at([1, 1])).unwrap();
+        assert_eq!(Game::state(&game), GameState::Playable);
+
+        game.make_move(&at([2, 2])).unwrap();
+        assert_eq!(Game::state(&game), GameState::Win(ImpartialPlayer::Next));
+    }
+
+    #[test]
+    fn moves_round_trip() {
+        crate::util::assert_moves_round_trip(&Notakto::new(2, 3, 3), 2);
+    }
+
+    #[test]
+    fn invalid_win_lengths() {
+        let args = |win_length| NotaktoArgs {
+            dimensions: 2,
+            size: 3,
+            win_length: Some(win_length),
+            moves: vec![],
+        };
+
+        assert!(Notakto::try_from(args(0)).is_err());
+        assert!(Notakto::try_from(args(4)).is_err());
+        assert!(Notakto::try_from(args(3)).is_ok());
+    }
+}
@@ -12,7 +12,8 @@ use game_solver::{
 use serde::{Deserialize, Serialize};
 use std::{
     fmt::{Debug, Display, Formatter},
-    hash::Hash, str::FromStr,
+    hash::Hash,
+    str::FromStr,
 };
 use thiserror::Error;
 
@@ -84,6 +85,35 @@ impl<
     }
 }
 
+impl<
+        const WIDTH: usize,
+        const HEIGHT: usize,
+        const MIN_WIN_LENGTH: usize,
+        const MAX_WIN_LENGTH: usize,
+    > OrderAndChaos<WIDTH, HEIGHT, MIN_WIN_LENGTH, MAX_WIN_LENGTH>
+{
+    /// Every line of `MIN_WIN_LENGTH` cells that fits on the board,
+    /// as its starting (row, column) and its (row, column) direction:
+    /// horizontal, vertical, diagonal, and anti-diagonal.
+    fn lines() -> impl Iterator<Item = ((usize, usize), (isize, isize))> {
+        let span = MIN_WIN_LENGTH as isize - 1;
+
+        (0..HEIGHT)
+            .flat_map(|row| (0..WIDTH).map(move |column| (row, column)))
+            .flat_map(|start| {
+                [(0, 1), (1, 0), (1, 1), (1, -1)]
+                    .into_iter()
+                    .map(move |direction| (start, direction))
+            })
+            .filter(move |((row, column), (row_direction, column_direction))| {
+                let end_row = *row as isize + span * row_direction;
+                let end_column = *column as isize + span * column_direction;
+
+                (0..HEIGHT as isize).contains(&end_row) && (0..WIDTH as isize).contains(&end_column)
+            })
+    }
+}
+
 #[derive(Error, Clone, Debug)]
 pub enum OrderAndChaosMoveError {
     #[error("Can not make move {played:?} as it is out of bounds of (w:{width},h:{height})")]
@@ -101,7 +131,7 @@ pub struct OrderAndChaosMove(((usize, usize), CellType));
 
 impl Display for OrderAndChaosMove {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{} @ ({}, {})", self.0 .1, self.0 .0 .0, self.0 .0 .1)
+        write!(f, "{}-{}-{}", self.0 .0 .0, self.0 .0 .1, self.0 .1)
     }
 }
 
@@ -121,6 +151,7 @@ impl<
     /// and Right is Chaos
     type Player = PartizanPlayer;
     type MoveError = OrderAndChaosMoveError;
+    type Key = Self;
 
     fn max_moves(&self) -> Option<usize> {
         Some(WIDTH * HEIGHT)
@@ -174,76 +205,38 @@ impl<
             return GameState::Playable;
         }
 
-        // check every horizontal row
-        for i in 0..HEIGHT {
-            // for the first (some) elements in that row
-            // TODO: this will not work if min_width_length > width! can we consider this?
-            'row_check: for j in 0..=(WIDTH - MIN_WIN_LENGTH) {
-                // find a piece? lets see if it continues going
-                if let Some(cell_type) = self.board[(j, i)] {
-                    for k in (j + 1)..((MIN_WIN_LENGTH + j).min(WIDTH)) {
-                        if let Some(found_cell_type) = self.board[(k, i)] {
-                            if cell_type == found_cell_type {
-                                continue;
-                            } else {
-                                break 'row_check;
-                            }
-                        } else {
-                            break 'row_check;
-                        }
-                    }
-
-                    return GameState::Win(PartizanPlayer::Left);
+        // whether there's a line that can still be filled with only one cell type
+        let mut line_possible = false;
+
+        for ((row, column), (row_direction, column_direction)) in Self::lines() {
+            let (mut x_count, mut o_count) = (0, 0);
+
+            for k in 0..MIN_WIN_LENGTH as isize {
+                let cell = self.board[(
+                    (row as isize + k * row_direction) as usize,
+                    (column as isize + k * column_direction) as usize,
+                )];
+
+                match cell {
+                    Some(CellType::X) => x_count += 1,
+                    Some(CellType::O) => o_count += 1,
+                    None => (),
                 }
             }
-        }
 
-        // check every column
-        for i in 0..WIDTH {
-            'column_check: for j in 0..=(HEIGHT - MIN_WIN_LENGTH) {
-                // find a piece? see if it continues going
-                if let Some(cell_type) = self.board[(i, j)] {
-                    for k in (j + 1)..((MIN_WIN_LENGTH + j).min(HEIGHT)) {
-                        if let Some(found_cell_type) = self.board[(i, k)] {
-                            if cell_type == found_cell_type {
-                                continue;
-                            } else {
-                                break 'column_check;
-                            }
-                        } else {
-                            break 'column_check;
-                        }
-                    }
-
-                    return GameState::Win(PartizanPlayer::Left);
-                }
+            // both players place both cell types, so Order wins
+            // no matter who completed the line.
+            if x_count == MIN_WIN_LENGTH || o_count == MIN_WIN_LENGTH {
+                return GameState::Win(PartizanPlayer::Left);
             }
-        }
 
-        // check every diag - we can essentially
-        // check every value in the top right corner
-        for i in 0..=(WIDTH - MIN_WIN_LENGTH) {
-            'diag_check: for j in 0..=(HEIGHT - MIN_WIN_LENGTH) {
-                if let Some(cell_type) = self.board[(i, j)] {
-                    // found a cell! lets continue going down!
-                    for k in 1..MIN_WIN_LENGTH.min(WIDTH).min(HEIGHT) {
-                        if let Some(found_cell_type) = self.board[(i + k, j + k)] {
-                            if found_cell_type == cell_type {
-                                continue;
-                            } else {
-                                break 'diag_check;
-                            }
-                        } else {
-                            break 'diag_check;
-                        }
-                    }
-
-                    return GameState::Win(PartizanPlayer::Left);
-                }
+            if x_count == 0 || o_count == 0 {
+                line_possible = true;
             }
         }
 
-        if self.move_count == WIDTH * HEIGHT {
+        // chaos wins once every line is blocked - this includes a full board.
+        if !line_possible {
             return GameState::Win(PartizanPlayer::Right);
         }
 
@@ -296,17 +289,49 @@ impl<
 /// Analyzes Order and Chaos.
 ///
 #[doc = include_str!("./README.md")]
-#[derive(Args, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Default)]
+#[derive(Args, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
 pub struct OrderAndChaosArgs {
+    /// The width of the board
+    #[arg(long, default_value_t = 6)]
+    width: usize,
+    /// The height of the board
+    #[arg(long, default_value_t = 6)]
+    height: usize,
+    /// The amount of pieces in a row Order needs to win
+    #[arg(long, default_value_t = 5)]
+    win_length: usize,
+    /// Order and Chaos moves, ordered as x1-y1-cell1 x2-y2-cell2 ...
     moves: Vec<String>,
 }
 
+impl OrderAndChaosArgs {
+    /// The width, height, and win length of the board, in that order.
+    pub fn board_size(&self) -> (usize, usize, usize) {
+        (self.width, self.height, self.win_length)
+    }
+}
+
+impl Default for OrderAndChaosArgs {
+    fn default() -> Self {
+        Self {
+            width: 6,
+            height: 6,
+            win_length: 5,
+            moves: vec![],
+        }
+    }
+}
+
 impl FromStr for OrderAndChaosMove {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let args: Vec<&str> = s.split('-').collect();
 
+        if args.len() != 3 {
+            return Err(anyhow!("move must be separated as `row-column-cell`"));
+        }
+
         let numbers = args[0..2]
             .iter()
             .map(|num| num.parse::<usize>().expect("Not a number!"))
@@ -318,8 +343,6 @@ impl FromStr for OrderAndChaosMove {
             _ => Err(anyhow!("Invalid player!")),
         }?;
 
-        assert_eq!(args.len(), 3);
-
         Ok(OrderAndChaosMove(((numbers[0], numbers[1]), player)))
     }
 }
@@ -334,6 +357,15 @@ impl<
     type Error = Error;
 
     fn try_from(value: OrderAndChaosArgs) -> Result<Self, Self::Error> {
+        if value.board_size() != (WIDTH, HEIGHT, MIN_WIN_LENGTH) {
+            return Err(anyhow!(
+                "expected a {WIDTH}x{HEIGHT} board with a win length of {MIN_WIN_LENGTH}, but got {}x{} with a win length of {}.",
+                value.width,
+                value.height,
+                value.win_length
+            ));
+        }
+
         let mut game = OrderAndChaos::new();
 
         // parse every move in args, e.g. 0-0-x 1-1-o in args
@@ -458,6 +490,61 @@ mod tests {
         assert_eq!(diagonal_board.state(), GameState::Win(PartizanPlayer::Left));
     }
 
+    #[test]
+    fn win_anti_diagonal() {
+        let corner_board = from_string(
+            ".....O\
+        ....O.\
+        ...O..\
+        ..O...\
+        .O....\
+        ......",
+        );
+
+        assert_eq!(corner_board.state(), GameState::Win(PartizanPlayer::Left));
+
+        let offset_board = from_string(
+            "......\
+        .....X\
+        ....X.\
+        ...X..\
+        ..X...\
+        .X....",
+        );
+
+        assert_eq!(offset_board.state(), GameState::Win(PartizanPlayer::Left));
+    }
+
+    #[test]
+    fn lose_anti_diagonal_tiny() {
+        let anti_diagonal_board = from_string(
+            "......\
+        .....X\
+        ....X.\
+        ...O..\
+        ..X...\
+        .X....",
+        );
+
+        assert_eq!(anti_diagonal_board.state(), GameState::Playable);
+    }
+
+    #[test]
+    fn chaos_wins_early() {
+        // every line has both cell types, even with the corners left empty
+        let blocked_board = from_string(
+            ".XOXO.\
+        OOXXOX\
+        OOXOOX\
+        XOXXXX\
+        OXOXXO\
+        .XOXX.",
+        );
+
+        assert_eq!(blocked_board.move_count, 32);
+        assert_eq!(blocked_board.state(), GameState::Win(PartizanPlayer::Right));
+    }
+
     #[test]
     fn lose_diagonal_tiny() {
         let diagonal_board = from_string(
@@ -471,4 +558,25 @@ mod tests {
 
         assert_eq!(diagonal_board.state(), GameState::Playable);
     }
+
+    #[test]
+    fn args_board_size() {
+        let args = OrderAndChaosArgs {
+            width: 4,
+            height: 4,
+            win_length: 4,
+            moves: vec!["0-0-x".to_string()],
+        };
+
+        let game = OrderAndChaos::<4, 4, 4, 4>::try_from(args.clone()).unwrap();
+        assert_eq!(game.move_count(), 1);
+
+        assert!(OrderAndChaos::<4, 4, 3, 4>::try_from(args.clone()).is_err());
+        assert!(OrderAndChaos::<6, 6, 5, 6>::try_from(args).is_err());
+    }
+
+    #[test]
+    fn moves_round_trip() {
+        crate::util::assert_moves_round_trip(&OrderAndChaos::<4, 4, 4, 4>::new(), 2);
+    }
 }
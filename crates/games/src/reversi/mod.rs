@@ -3,7 +3,7 @@
 #[cfg(feature = "egui")]
 pub mod gui;
 
-use anyhow::Error;
+use anyhow::{anyhow, Error};
 use array2d::Array2D;
 use clap::Args;
 use game_solver::{
@@ -11,59 +11,131 @@ use game_solver::{
     player::{PartizanPlayer, Player},
 };
 use serde::{Deserialize, Serialize};
-use std::fmt::{self, Debug};
+use std::fmt::{self, Debug, Display};
 use std::hash::Hash;
+use std::str::FromStr;
+use thiserror::Error;
 
 use crate::util::{move_failable, move_natural::NaturalMove};
 
-pub const WIDTH: usize = 6;
-pub const HEIGHT: usize = 6;
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ReversiMove {
+    /// Places a piece at a position, flipping the pieces it surrounds.
+    Place(NaturalMove<2>),
+    /// Skips a turn, which is only allowed if no pieces can be placed.
+    Pass,
+}
+
+impl FromStr for ReversiMove {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "pass" {
+            Ok(Self::Pass)
+        } else {
+            NaturalMove::from_str(s).map(Self::Place)
+        }
+    }
+}
 
-pub type ReversiMove = NaturalMove<2>;
+impl Display for ReversiMove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Place(position) => write!(f, "{position}"),
+            Self::Pass => write!(f, "pass"),
+        }
+    }
+}
+
+#[derive(Error, Debug, Clone)]
+pub enum ReversiMoveError {
+    #[error("{0} is off the board.")]
+    OutOfBounds(NaturalMove<2>),
+    #[error("placing a piece at {0} doesn't flip any pieces.")]
+    InvalidPlacement(NaturalMove<2>),
+    #[error("can't pass when a piece can be placed.")]
+    InvalidPass,
+}
 
 #[derive(Clone, Hash, Eq, PartialEq)]
 pub struct Reversi {
+    width: usize,
+    height: usize,
     /// None if empty, Some(Player) if occupied
     board: Array2D<Option<PartizanPlayer>>,
     move_count: usize,
 }
 
 impl Reversi {
-    fn new() -> Self {
-        let mut board = Array2D::filled_with(None, WIDTH, HEIGHT);
+    /// Create a new game of Reversi with the four starting pieces in the center of the board.
+    ///
+    /// Both dimensions must be at least 2.
+    pub fn new(width: usize, height: usize) -> Self {
+        assert!(
+            width >= 2 && height >= 2,
+            "a {width}x{height} board can't fit the starting pieces"
+        );
+
+        let mut board = Array2D::filled_with(None, width, height);
 
         // set middle squares to occupied:
         board
-            .set(WIDTH / 2 - 1, HEIGHT / 2 - 1, Some(PartizanPlayer::Left))
+            .set(width / 2 - 1, height / 2 - 1, Some(PartizanPlayer::Left))
             .unwrap();
         board
-            .set(WIDTH / 2, HEIGHT / 2, Some(PartizanPlayer::Left))
+            .set(width / 2, height / 2, Some(PartizanPlayer::Left))
             .unwrap();
         board
-            .set(WIDTH / 2 - 1, HEIGHT / 2, Some(PartizanPlayer::Right))
+            .set(width / 2 - 1, height / 2, Some(PartizanPlayer::Right))
             .unwrap();
         board
-            .set(WIDTH / 2, HEIGHT / 2 - 1, Some(PartizanPlayer::Right))
+            .set(width / 2, height / 2 - 1, Some(PartizanPlayer::Right))
             .unwrap();
 
         Self {
+            width,
+            height,
             board,
             move_count: 0,
         }
     }
 
+    /// Returns the amount of pieces (Left, Right) have on the board.
+    fn piece_counts(&self) -> (usize, usize) {
+        let mut player_one_count = 0;
+        let mut player_two_count = 0;
+
+        for x in 0..self.width {
+            for y in 0..self.height {
+                match *self.board.get(x, y).unwrap() {
+                    Some(PartizanPlayer::Left) => player_one_count += 1,
+                    Some(PartizanPlayer::Right) => player_two_count += 1,
+                    None => (),
+                }
+            }
+        }
+
+        (player_one_count, player_two_count)
+    }
+
     fn on_board(&self, x: usize, y: usize) -> bool {
-        x < WIDTH && y < HEIGHT
+        x < self.width && y < self.height
     }
 
-    fn is_valid_move(&self, m: &<Self as Game>::Move) -> Option<Vec<<Self as Game>::Move>> {
-        let cell = *self.board.get(m.0[0], m.0[1]).unwrap();
+    /// Returns the pieces that would be flipped by `player` placing a piece at `m`,
+    /// or `None` if the placement isn't valid - including placements off the board.
+    fn is_valid_move(
+        &self,
+        m: &NaturalMove<2>,
+        player: PartizanPlayer,
+    ) -> Option<Vec<NaturalMove<2>>> {
+        let cell = *self.board.get(m.0[0], m.0[1])?;
 
         if cell.is_some() {
             return None;
         }
 
-        let opposing_tile = self.player().next();
+        let opposing_tile = player.next();
 
         let mut tiles_to_flip = Vec::new();
 
@@ -105,7 +177,7 @@ impl Reversi {
                 continue;
             }
 
-            if self.board.get(x, y) == Some(&Some(self.player())) {
+            if self.board.get(x, y) == Some(&Some(player)) {
                 loop {
                     x = x.checked_add_signed(-*x_dir).unwrap();
                     y = y.checked_add_signed(-*y_dir).unwrap();
@@ -125,16 +197,31 @@ impl Reversi {
             Some(tiles_to_flip)
         }
     }
+
+    /// Returns every position `player` can place a piece at.
+    fn placements(&self, player: PartizanPlayer) -> Vec<NaturalMove<2>> {
+        let mut moves = Vec::new();
+        for x in 0..self.width {
+            for y in 0..self.height {
+                if self.is_valid_move(&NaturalMove([x, y]), player).is_some() {
+                    moves.push(NaturalMove([x, y]));
+                }
+            }
+        }
+        moves
+    }
 }
 
 impl Game for Reversi {
     type Move = ReversiMove;
     type Iter<'a> = std::vec::IntoIter<Self::Move>;
     type Player = PartizanPlayer;
-    type MoveError = array2d::Error;
+    type Key = Self;
+    type MoveError = ReversiMoveError;
 
     fn max_moves(&self) -> Option<usize> {
-        Some(WIDTH * HEIGHT)
+        // every empty square can be filled, and every pass is followed by a placement
+        Some(2 * (self.width * self.height - 4))
     }
 
     fn move_count(&self) -> usize {
@@ -142,12 +229,31 @@ impl Game for Reversi {
     }
 
     fn make_move(&mut self, m: &Self::Move) -> Result<(), Self::MoveError> {
-        let move_set = self.is_valid_move(m).unwrap();
+        match m {
+            ReversiMove::Place(position) => {
+                if !self.on_board(position.0[0], position.0[1]) {
+                    return Err(ReversiMoveError::OutOfBounds(*position));
+                }
 
-        self.board.set(m.0[0], m.0[1], Some(self.player())).unwrap();
+                let move_set = self
+                    .is_valid_move(position, self.player())
+                    .ok_or(ReversiMoveError::InvalidPlacement(*position))?;
 
-        for idx in move_set {
-            self.board.set(idx.0[0], idx.0[1], Some(self.player()))?;
+                self.board
+                    .set(position.0[0], position.0[1], Some(self.player()))
+                    .unwrap();
+
+                for idx in move_set {
+                    self.board
+                        .set(idx.0[0], idx.0[1], Some(self.player()))
+                        .unwrap();
+                }
+            }
+            ReversiMove::Pass => {
+                if !self.placements(self.player()).is_empty() {
+                    return Err(ReversiMoveError::InvalidPass);
+                }
+            }
         }
 
         self.move_count += 1;
@@ -156,15 +262,19 @@ impl Game for Reversi {
     }
 
     fn possible_moves(&self) -> Self::Iter<'_> {
-        let mut moves = Vec::new();
-        for x in 0..WIDTH {
-            for y in 0..HEIGHT {
-                if self.is_valid_move(&NaturalMove([x, y])).is_some() {
-                    moves.push(NaturalMove([x, y]));
-                }
-            }
+        let placements = self.placements(self.player());
+
+        // a player has to pass if they can't place anything,
+        // but the game ends instead if neither player can.
+        if placements.is_empty() && !self.placements(self.player().next()).is_empty() {
+            return vec![ReversiMove::Pass].into_iter();
         }
-        moves.into_iter()
+
+        placements
+            .into_iter()
+            .map(ReversiMove::Place)
+            .collect::<Vec<_>>()
+            .into_iter()
     }
 
     fn state(&self) -> GameState<Self::Player> {
@@ -172,18 +282,7 @@ impl Game for Reversi {
             return GameState::Playable;
         }
 
-        let mut player_one_count = 0;
-        let mut player_two_count = 0;
-
-        for x in 0..WIDTH {
-            for y in 0..HEIGHT {
-                match *self.board.get(x, y).unwrap() {
-                    Some(PartizanPlayer::Left) => player_one_count += 1,
-                    Some(PartizanPlayer::Right) => player_two_count += 1,
-                    None => (),
-                }
-            }
-        }
+        let (player_one_count, player_two_count) = self.piece_counts();
 
         match player_one_count.cmp(&player_two_count) {
             std::cmp::Ordering::Greater => GameState::Win(PartizanPlayer::Left),
@@ -199,6 +298,26 @@ impl Game for Reversi {
             PartizanPlayer::Right
         }
     }
+
+    const HAS_HEURISTIC: bool = true;
+
+    /// The difference in piece count, from the perspective of the current player.
+    fn heuristic(&self) -> isize {
+        let (player_one_count, player_two_count) = self.piece_counts();
+        let difference = player_one_count as isize - player_two_count as isize;
+
+        match self.player() {
+            PartizanPlayer::Left => difference,
+            PartizanPlayer::Right => -difference,
+        }
+    }
+
+    /// Reversi is won by pieces, so larger wins are preferred.
+    const HAS_TERMINAL_VALUE: bool = true;
+
+    fn terminal_value(&self) -> Option<isize> {
+        Some(self.heuristic())
+    }
 }
 
 fn player_to_char(player: Option<PartizanPlayer>) -> char {
@@ -213,10 +332,10 @@ impl fmt::Display for Reversi {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "Current player: {}", player_to_char(Some(self.player())))?;
 
-        let moves = self.possible_moves().collect::<Vec<_>>();
+        let moves = self.placements(self.player());
 
-        for y in 0..HEIGHT {
-            for x in 0..WIDTH {
+        for y in 0..self.height {
+            for x in 0..self.width {
                 let character = if moves.contains(&NaturalMove([x, y])) {
                     '*'
                 } else {
@@ -241,18 +360,42 @@ impl Debug for Reversi {
 /// Analyzes Reversi.
 ///
 #[doc = include_str!("./README.md")]
-#[derive(Args, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Default)]
+#[derive(Args, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
 pub struct ReversiArgs {
+    /// The width of the board
+    #[arg(long, default_value_t = 6)]
+    width: usize,
+    /// The height of the board
+    #[arg(long, default_value_t = 6)]
+    height: usize,
     /// Reversi moves, ordered as x1-y1 x2-y2 ...
     #[arg(value_parser = clap::value_parser!(ReversiMove))]
     moves: Vec<ReversiMove>,
 }
 
+impl Default for ReversiArgs {
+    fn default() -> Self {
+        Self {
+            width: 6,
+            height: 6,
+            moves: vec![],
+        }
+    }
+}
+
 impl TryFrom<ReversiArgs> for Reversi {
     type Error = Error;
 
     fn try_from(value: ReversiArgs) -> Result<Self, Self::Error> {
-        let mut game = Reversi::new();
+        if value.width < 2 || value.height < 2 {
+            return Err(anyhow!(
+                "the board must be at least 2x2, but got {}x{}",
+                value.width,
+                value.height
+            ));
+        }
+
+        let mut game = Reversi::new(value.width, value.height);
 
         // parse every move in args, e.g. 0-0 1-1 in args
         for game_move in value.moves {
@@ -262,3 +405,233 @@ impl TryFrom<ReversiArgs> for Reversi {
         Ok(game)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        sync::{atomic::AtomicBool, Arc},
+        time::Duration,
+    };
+
+    use game_solver::{
+        game::{score_to_outcome, GameScoreOutcome},
+        solve, solve_depth_limited, solve_iterative_deepening, solve_with_timeout, SolveMode,
+    };
+
+    use super::*;
+
+    #[test]
+    fn small_board() {
+        let game = Reversi::new(4, 4);
+
+        assert_eq!(game.move_count(), 0);
+        assert_eq!(game.piece_counts(), (2, 2));
+        assert_eq!(
+            game.possible_moves().collect::<Vec<_>>(),
+            vec![
+                ReversiMove::Place(NaturalMove([0, 2])),
+                ReversiMove::Place(NaturalMove([1, 3])),
+                ReversiMove::Place(NaturalMove([2, 0])),
+                ReversiMove::Place(NaturalMove([3, 1]))
+            ]
+        );
+    }
+
+    #[test]
+    fn args_dimensions() {
+        let game = Reversi::try_from(ReversiArgs {
+            width: 8,
+            height: 8,
+            moves: vec![ReversiMove::Place(NaturalMove([2, 4]))],
+        })
+        .unwrap();
+
+        assert_eq!(game.move_count(), 1);
+        assert_eq!(game.piece_counts(), (4, 1));
+
+        assert!(Reversi::try_from(ReversiArgs {
+            width: 1,
+            height: 8,
+            moves: vec![],
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn forced_pass() {
+        let mut game = Reversi::new(4, 4);
+        game.board = Array2D::filled_with(None, 4, 4);
+        game.board.set(0, 0, Some(PartizanPlayer::Right)).unwrap();
+        game.board.set(1, 0, Some(PartizanPlayer::Left)).unwrap();
+
+        // Left can't flank the cornered piece, but Right can flank Left's piece
+        assert_eq!(game.state(), GameState::Playable);
+        assert_eq!(
+            game.possible_moves().collect::<Vec<_>>(),
+            vec![ReversiMove::Pass]
+        );
+        assert!(game
+            .clone()
+            .make_move(&ReversiMove::Place(NaturalMove([2, 0])))
+            .is_err());
+
+        // after Right takes every piece, neither player can move
+        assert_eq!(
+            solve(&game, &mut HashMap::new(), SolveMode::Strong, None).unwrap(),
+            -3
+        );
+
+        game.make_move(&ReversiMove::Pass).unwrap();
+        assert!(game.clone().make_move(&ReversiMove::Pass).is_err());
+        game.make_move(&ReversiMove::Place(NaturalMove([2, 0])))
+            .unwrap();
+
+        assert_eq!(game.possible_moves().len(), 0);
+        assert_eq!(game.state(), GameState::Win(PartizanPlayer::Right));
+    }
+
+    #[test]
+    fn move_parsing() {
+        assert_eq!(
+            ReversiMove::from_str("2-4").unwrap(),
+            ReversiMove::Place(NaturalMove([2, 4]))
+        );
+        assert_eq!(ReversiMove::from_str("pass").unwrap(), ReversiMove::Pass);
+        assert!(ReversiMove::from_str("2").is_err());
+    }
+
+    #[test]
+    fn depth_limited_heuristic() {
+        let game = Reversi::new(6, 6);
+
+        // the starting position is symmetric
+        assert_eq!(game.heuristic(), 0);
+
+        // any first move flips one piece, leaving the second player 4-1 behind.
+        // with one move of lookahead, the best the first player can do is that.
+        assert_eq!(
+            solve_depth_limited(&game, &mut HashMap::new(), 1, None, &None).unwrap(),
+            3
+        );
+    }
+
+    #[test]
+    fn iterative_deepening() {
+        let game = Reversi::new(6, 6);
+        let mut transposition_table = HashMap::new();
+
+        let iterations = solve_iterative_deepening(&game, &mut transposition_table, None, &None)
+            .take(3)
+            .collect::<Vec<_>>();
+
+        assert_eq!(iterations.len(), 3);
+        assert_eq!(iterations[0], (1, 3));
+        assert_eq!(
+            iterations
+                .iter()
+                .map(|(depth, _)| *depth)
+                .collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn iterative_deepening_cancelled() {
+        let game = Reversi::new(6, 6);
+        let token = Some(Arc::new(AtomicBool::new(true)));
+
+        assert_eq!(
+            solve_iterative_deepening(&game, &mut HashMap::new(), None, &token).count(),
+            0
+        );
+    }
+
+    #[test]
+    fn terminal_value_is_piece_margin() {
+        let mut game = Reversi::new(6, 6);
+        for x in 0..6 {
+            for y in 0..6 {
+                let player = if x < 4 {
+                    PartizanPlayer::Left
+                } else {
+                    PartizanPlayer::Right
+                };
+                game.board.set(x, y, Some(player)).unwrap();
+            }
+        }
+        game.move_count = 6 * 6 - 4;
+
+        // 24 pieces to 12, with the winner to move
+        assert_eq!(game.state(), GameState::Win(PartizanPlayer::Left));
+        assert_eq!(game.terminal_value(), Some(12));
+        assert_eq!(
+            solve(&game, &mut HashMap::new(), SolveMode::Strong, None).unwrap(),
+            12
+        );
+    }
+
+    #[test]
+    fn off_board_placements() {
+        let mut game = Reversi::new(6, 6);
+
+        for position in [[6, 0], [0, 6], [usize::MAX, 2]] {
+            assert!(matches!(
+                game.make_move(&ReversiMove::Place(NaturalMove(position))),
+                Err(ReversiMoveError::OutOfBounds(_))
+            ));
+        }
+        assert!(game
+            .is_valid_move(&NaturalMove([6, 6]), PartizanPlayer::Left)
+            .is_none());
+        assert_eq!(game.move_count(), 0);
+    }
+
+    #[test]
+    fn margin_scores_have_no_distance() {
+        let mut game = Reversi::new(6, 6);
+        for x in 0..6 {
+            for y in 0..6 {
+                let player = if x < 4 {
+                    PartizanPlayer::Left
+                } else {
+                    PartizanPlayer::Right
+                };
+                game.board.set(x, y, Some(player)).unwrap();
+            }
+        }
+        // a game with plenty of passes, so the margin outweighs the moves left
+        game.move_count = 60;
+
+        let score = solve(&game, &mut HashMap::new(), SolveMode::Strong, None).unwrap();
+        assert_eq!(score, 12);
+        assert_eq!(
+            score_to_outcome(&game, score),
+            GameScoreOutcome::Win { distance: None }
+        );
+        assert_eq!(
+            score_to_outcome(&game, -score),
+            GameScoreOutcome::Loss { distance: None }
+        );
+    }
+
+    #[test]
+    fn timeout() {
+        // 6x6 reversi can't be solved in a few milliseconds
+        assert_eq!(
+            solve_with_timeout(
+                &Reversi::new(6, 6),
+                &mut HashMap::new(),
+                Duration::from_millis(50),
+                None
+            )
+            .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn moves_round_trip() {
+        crate::util::assert_moves_round_trip(&Reversi::new(4, 4), 2);
+    }
+}
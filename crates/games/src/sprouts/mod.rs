@@ -15,6 +15,7 @@ use game_solver::{
 use itertools::Itertools;
 use petgraph::{
     matrix_graph::{MatrixGraph, NodeIndex},
+    unionfind::UnionFind,
     visit::{IntoEdgeReferences, IntoNodeIdentifiers},
     Undirected,
 };
@@ -34,7 +35,7 @@ pub struct SproutsMove {
 
 impl Display for SproutsMove {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "({} {})", self.from.index(), self.to.index())
+        write!(f, "{}-{}", self.from.index(), self.to.index())
     }
 }
 
@@ -46,7 +47,8 @@ pub struct Sprouts(SproutsGraph);
 // SproutsGraph, given that its vertices and edges are unlabelled,
 // doesn't implement equality as that requires isomorphism checks.
 // since we don't want these operations for reordering to be expensive,
-// we simply check for equality as is.
+// we simply check for equality as is - isomorphic positions are instead
+// merged in the transposition table through their `SproutsKey`.
 
 impl Hash for Sprouts {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
@@ -77,6 +79,154 @@ impl Sprouts {
 
         Self(graph)
     }
+
+    /// Colors every sprout by an isomorphism invariant, refining the colors by the colors
+    /// of each sprout's neighbours until no more sprouts can be told apart.
+    ///
+    /// Colors only depend on the shape of the position, not on how its sprouts are labelled.
+    fn refined_colors(&self) -> Vec<usize> {
+        let neighbours = (0..self.0.node_count())
+            .map(|node| {
+                self.0
+                    .neighbors(NodeIndex::new(node))
+                    .map(|neighbour| neighbour.index())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let mut colors = rank(
+            &(0..self.0.node_count())
+                .map(|node| {
+                    let node = NodeIndex::new(node);
+                    (self.0.edges(node).count(), self.0.has_edge(node, node))
+                })
+                .collect::<Vec<_>>(),
+        );
+
+        loop {
+            let refined = rank(
+                &neighbours
+                    .iter()
+                    .enumerate()
+                    .map(|(node, neighbours)| {
+                        let neighbour_colors = neighbours
+                            .iter()
+                            .map(|&neighbour| colors[neighbour])
+                            .sorted()
+                            .collect::<Vec<_>>();
+                        (colors[node], neighbour_colors)
+                    })
+                    .collect::<Vec<_>>(),
+            );
+
+            // refining never merges colors, so an equal amount of colors means nothing changed
+            if refined.iter().max() == colors.iter().max() {
+                return colors;
+            }
+
+            colors = refined;
+        }
+    }
+
+    /// Relabels the sprouts of this position so that every isomorphic position
+    /// has the same edges, returning them in order.
+    fn canonical_edges(&self) -> Vec<(usize, usize)> {
+        if self.0.node_count() == 0 {
+            return vec![];
+        }
+
+        // sprouts are ordered by their refined color first,
+        // so only sprouts that share it have to be permuted.
+        let colors = self.refined_colors();
+        let nodes = self
+            .0
+            .node_identifiers()
+            .sorted_by_key(|node| colors[node.index()])
+            .collect::<Vec<_>>();
+        let classes = nodes
+            .chunk_by(|a, b| colors[a.index()] == colors[b.index()])
+            .collect::<Vec<_>>();
+
+        // sprouts without any lines are interchangeable, so they never have to be permuted
+        let permuted = |class: &[NodeIndex<SproutsIx>]| self.0.edges(class[0]).next().is_some();
+        let relabelings = classes
+            .iter()
+            .filter(|class| permuted(class))
+            .try_fold(1usize, |relabelings, class| {
+                (1..=class.len()).try_fold(relabelings, usize::checked_mul)
+            });
+
+        // past that, keeping the order of the colors is still a valid key,
+        // but isomorphic positions with ties left may not share it
+        let brute_force = relabelings.is_some_and(|relabelings| relabelings <= MAX_RELABELINGS);
+
+        let mut smallest: Option<Vec<(usize, usize)>> = None;
+
+        for labeling in classes
+            .iter()
+            .map(|class| {
+                let arrangements = if brute_force && permuted(class) {
+                    usize::MAX
+                } else {
+                    1
+                };
+                class.iter().permutations(class.len()).take(arrangements)
+            })
+            .multi_cartesian_product()
+        {
+            let mut labels = vec![0; self.0.node_count()];
+            for (label, node) in labeling.into_iter().flatten().enumerate() {
+                labels[node.index()] = label;
+            }
+
+            let edges = self
+                .0
+                .edge_references()
+                .map(|(from, to, _)| {
+                    let (from, to) = (labels[from.index()], labels[to.index()]);
+                    (from.min(to), from.max(to))
+                })
+                .sorted()
+                .collect::<Vec<_>>();
+
+            if smallest.as_ref().map_or(true, |smallest| edges < *smallest) {
+                smallest = Some(edges);
+            }
+        }
+
+        smallest.unwrap()
+    }
+}
+
+/// The most relabelings of sprouts that share a color [`Sprouts::canonical_edges`] tries,
+/// before it gives up on merging every isomorphic position.
+const MAX_RELABELINGS: usize = 720;
+
+/// Replaces every value with its rank among the distinct values.
+fn rank<T: Ord>(values: &[T]) -> Vec<usize> {
+    let distinct = values.iter().sorted().dedup().collect::<Vec<_>>();
+
+    values
+        .iter()
+        .map(|value| distinct.binary_search(&value).unwrap())
+        .collect()
+}
+
+/// The transposition table key of a [`Sprouts`] position,
+/// which every position isomorphic to it shares.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SproutsKey {
+    node_count: usize,
+    edges: Vec<(usize, usize)>,
+}
+
+impl From<Sprouts> for SproutsKey {
+    fn from(game: Sprouts) -> Self {
+        Self {
+            node_count: game.0.node_count(),
+            edges: game.canonical_edges(),
+        }
+    }
 }
 
 #[derive(Error, Debug, Clone)]
@@ -92,12 +242,66 @@ pub enum SproutsMoveError {
 const MAX_SPROUTS: usize = 3;
 
 impl Normal for Sprouts {}
-impl NormalImpartial for Sprouts {}
+impl NormalImpartial for Sprouts {
+    /// Splits off every connected group of dead sprouts.
+    ///
+    /// Any two live sprouts can be connected by a move, so every sprout that is still
+    /// alive (and everything connected to it) has to stay in the same game.
+    fn split(&self) -> Option<Vec<Self>> {
+        let mut components = UnionFind::<usize>::new(self.0.node_count());
+
+        for (from, to, _) in self.0.edge_references() {
+            components.union(from.index(), to.index());
+        }
+
+        let mut live = self
+            .0
+            .node_identifiers()
+            .filter(|&id| self.0.edges(id).count() < MAX_SPROUTS);
+        if let Some(first) = live.next() {
+            for id in live {
+                components.union(first.index(), id.index());
+            }
+        }
+
+        let labels = components.into_labeling();
+        let groups = self
+            .0
+            .node_identifiers()
+            .into_group_map_by(|id| labels[id.index()]);
+
+        if groups.len() < 2 {
+            return None;
+        }
+
+        Some(
+            groups
+                .into_values()
+                .sorted()
+                .map(|nodes| {
+                    let mut game = Sprouts::new(nodes.len().try_into().unwrap());
+                    let label = |id: NodeIndex<SproutsIx>| {
+                        NodeIndex::new(nodes.iter().position(|&node| node == id).unwrap())
+                    };
+
+                    for (from, to, _) in self.0.edge_references() {
+                        if nodes.contains(&from) {
+                            game.0.add_edge(label(from), label(to), ());
+                        }
+                    }
+
+                    game
+                })
+                .collect(),
+        )
+    }
+}
 impl Game for Sprouts {
     type Move = SproutsMove;
     type Iter<'a> = std::vec::IntoIter<Self::Move>;
 
     type Player = ImpartialPlayer;
+    type Key = SproutsKey;
     type MoveError = SproutsMoveError;
 
     fn max_moves(&self) -> Option<usize> {
@@ -215,7 +419,7 @@ impl Game for Sprouts {
     }
 
     fn state(&self) -> game_solver::game::GameState<Self::Player> {
-        <Self as Normal>::state(&self)
+        <Self as Normal>::state(self)
     }
 }
 
@@ -298,3 +502,124 @@ impl FromStr for SproutsMove {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        hash::{DefaultHasher, Hasher},
+    };
+
+    use game_solver::{grundy_value, solve, solve_impartial_split, split_grundy_value, SolveMode};
+
+    use super::*;
+
+    fn play(node_count: SproutsIx, moves: &[(SproutsIx, SproutsIx)]) -> Sprouts {
+        let mut game = Sprouts::new(node_count);
+        for &(from, to) in moves {
+            game.make_move(&SproutsMove {
+                from: from.into(),
+                to: to.into(),
+            })
+            .unwrap();
+        }
+        game
+    }
+
+    fn hash(key: &SproutsKey) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn isomorphic_positions_hash_equal() {
+        let first = play(2, &[(0, 0)]);
+        let second = play(2, &[(1, 1)]);
+
+        assert_ne!(first, second);
+        assert_eq!(first.key(), second.key());
+        assert_eq!(hash(&first.key()), hash(&second.key()));
+    }
+
+    #[test]
+    fn distinct_positions_stay_distinct() {
+        let looped = play(2, &[(0, 0)]);
+        let connected = play(2, &[(0, 1)]);
+
+        assert_ne!(looped.key(), connected.key());
+        assert_eq!(
+            play(3, &[(0, 1), (1, 1)]).key(),
+            play(3, &[(1, 2), (2, 2)]).key()
+        );
+    }
+
+    #[test]
+    fn large_positions_key_quickly() {
+        // every untouched sprout shares a color,
+        // which brute force would have to permute factorially
+        assert_eq!(Sprouts::new(40).key(), Sprouts::new(40).key());
+
+        assert_eq!(
+            play(12, &[(0, 1), (2, 3), (4, 5), (6, 6)]).key(),
+            play(12, &[(11, 11), (9, 10), (7, 8), (5, 6)]).key()
+        );
+        assert_ne!(
+            play(12, &[(0, 1), (2, 3), (4, 5), (6, 6)]).key(),
+            play(12, &[(0, 1), (1, 2), (4, 5), (6, 6)]).key()
+        );
+    }
+
+    #[test]
+    fn split_dead_clusters() {
+        // a triangle of sprouts that all have loops is dead
+        let dead = [(0, 1), (1, 2), (0, 2), (0, 0), (1, 1), (2, 2)];
+        let live = [(3, 4)];
+        let game = play(6, &[&dead[..], &live[..]].concat());
+
+        let components = game.split().unwrap();
+        assert_eq!(components.len(), 2);
+        assert!(components
+            .iter()
+            .all(|component| component.0.node_count() == 3));
+        assert_eq!(components[0], play(3, &dead));
+        assert_eq!(components[1], play(3, &[(0, 1)]));
+
+        assert_eq!(
+            split_grundy_value(&game, &mut HashMap::new()).unwrap(),
+            grundy_value(&game, &mut HashMap::new()).unwrap()
+        );
+    }
+
+    #[test]
+    fn live_sprouts_dont_split() {
+        assert!(Sprouts::new(3).split().is_none());
+        assert!(play(3, &[(0, 1)]).split().is_none());
+    }
+
+    #[test]
+    fn solve_split() {
+        let dead = [(0, 1), (1, 2), (0, 2), (0, 0), (1, 1), (2, 2)];
+        for live in [&[][..], &[(3, 4)][..], &[(3, 3), (4, 5)][..]] {
+            let game = play(6, &[&dead[..], live].concat());
+            assert!(game.split().is_some());
+
+            assert_eq!(
+                solve_impartial_split(&game, &mut HashMap::new()).unwrap(),
+                solve(&game, &mut HashMap::new(), SolveMode::Strong, None).unwrap() > 0
+            );
+        }
+
+        // games that don't split are solved directly
+        let game = Sprouts::new(2);
+        assert_eq!(
+            solve_impartial_split(&game, &mut HashMap::new()).unwrap(),
+            solve(&game, &mut HashMap::new(), SolveMode::Strong, None).unwrap() > 0
+        );
+    }
+
+    #[test]
+    fn moves_round_trip() {
+        crate::util::assert_moves_round_trip(&Sprouts::new(3), 2);
+    }
+}
@@ -1 +1,83 @@
+use std::collections::HashMap;
 
+use egui::{vec2, Button, Color32, Grid, Ui};
+use ndarray::{Dimension, IntoDimension};
+
+use crate::util::gui::{egui_display::EguiDisplay, move_cell::MoveToCell};
+
+use super::{Square, TicTacToe, TicTacToeMove};
+
+impl TicTacToe {
+    /// Renders a two-dimensional board as a grid of squares,
+    /// labelling the empty squares that have a score in `scores` with it,
+    /// and filling the squares in `colors` with their color.
+    ///
+    /// Empty squares can only be clicked if `interactive` is set.
+    ///
+    /// # Returns
+    ///
+    /// The move for the empty square that was clicked, if any.
+    pub fn board_ui(
+        &self,
+        ui: &mut Ui,
+        interactive: bool,
+        scores: &[(TicTacToeMove, isize)],
+        colors: &HashMap<(usize, usize), Color32>,
+    ) -> Option<TicTacToeMove> {
+        if self.dim != 2 {
+            ui.label("Only two-dimensional boards can be shown.");
+            return None;
+        }
+
+        let mut clicked = None;
+
+        Grid::new("tic_tac_toe_board").show(ui, |ui| {
+            for row in 0..self.size {
+                for column in 0..self.size {
+                    let point = vec![row, column].into_dimension();
+                    let square = self.board[&point];
+
+                    let text = match square {
+                        Some(Square::X) => "X".to_string(),
+                        Some(Square::O) => "O".to_string(),
+                        None => scores
+                            .iter()
+                            .find(|(m, _)| m.0 == point)
+                            .map(|(_, score)| score.to_string())
+                            .unwrap_or_default(),
+                    };
+
+                    let mut button = Button::new(text).min_size(vec2(40.0, 40.0));
+                    if let Some(&color) = colors.get(&(row, column)) {
+                        button = button.fill(color);
+                    }
+
+                    if ui
+                        .add_enabled(interactive && square.is_none(), button)
+                        .clicked()
+                    {
+                        clicked = Some(TicTacToeMove(point));
+                    }
+                }
+                ui.end_row();
+            }
+        });
+
+        clicked
+    }
+}
+
+impl EguiDisplay for TicTacToe {
+    fn display(&self, ui: &mut Ui) {
+        self.board_ui(ui, false, &[], &HashMap::new());
+    }
+}
+
+impl MoveToCell for TicTacToe {
+    fn move_to_cell(&self, m: &TicTacToeMove) -> Option<(usize, usize)> {
+        match m.0.slice() {
+            &[row, column] => Some((row, column)),
+            _ => None,
+        }
+    }
+}
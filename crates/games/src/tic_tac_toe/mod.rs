@@ -5,23 +5,24 @@ pub mod gui;
 use anyhow::{anyhow, Error};
 use clap::Args;
 use game_solver::{
-    game::{Game, GameState},
-    player::{PartizanPlayer, Player},
+    game::{naive_immediately_resolvable_game, Game, GameState},
+    player::PartizanPlayer,
 };
 use itertools::Itertools;
-use ndarray::{iter::IndexedIter, ArrayD, Dim, Dimension, IntoDimension, IxDyn, IxDynImpl};
+use ndarray::{iter::IndexedIter, ArrayD, Axis, Dim, Dimension, IntoDimension, IxDyn, IxDynImpl};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use std::{
     fmt::{Debug, Display, Formatter},
     hash::Hash,
-    iter::FilterMap, str::FromStr,
+    iter::FilterMap,
+    str::FromStr,
 };
 
 use crate::util::move_failable;
 
-#[derive(Clone, Copy, Hash, Eq, PartialEq, Debug)]
+#[derive(Clone, Copy, Hash, Eq, PartialEq, PartialOrd, Ord, Debug)]
 pub enum Square {
     X,
     O,
@@ -47,6 +48,8 @@ impl Square {
 pub struct TicTacToe {
     dim: usize,
     size: usize,
+    /// The amount of squares in a row needed to win
+    win_length: usize,
     /// True represents a square that has not been eaten
     board: ArrayD<Option<Square>>,
     move_count: usize,
@@ -77,6 +80,9 @@ pub struct TicTacToeArgs {
     /// * * *
     /// ```
     size: usize,
+    /// The amount of squares in a row needed to win - defaults to the size of the board.
+    #[arg(long)]
+    win_length: Option<usize>,
     /// The moves to make in the game, by dimension and index in that dimension.
     moves: Vec<String>,
 }
@@ -86,6 +92,7 @@ impl Default for TicTacToeArgs {
         Self {
             dimensions: 2,
             size: 3,
+            win_length: None,
             moves: vec![],
         }
     }
@@ -99,7 +106,7 @@ impl FromStr for TicTacToeMove {
             .split('-')
             .map(|num| num.parse::<usize>().map_err(|_| anyhow!("Not a number!")))
             .collect();
-        
+
         Ok(Self(numbers?.into_dimension()))
     }
 }
@@ -108,7 +115,10 @@ impl TryFrom<TicTacToeArgs> for TicTacToe {
     type Error = Error;
 
     fn try_from(value: TicTacToeArgs) -> Result<Self, Self::Error> {
-        let mut game = TicTacToe::new(value.dimensions, value.size);
+        let win_length = value.win_length.unwrap_or(value.size);
+        check_win_length(value.size, win_length)?;
+
+        let mut game = TicTacToe::new(value.dimensions, value.size, win_length);
 
         // parse every move in args, e.g. 0-0 1-1 in args
         for arg in value.moves {
@@ -132,57 +142,118 @@ fn add_checked(a: Dim<IxDynImpl>, b: Vec<i32>) -> Option<Dim<IxDynImpl>> {
     Some(result)
 }
 
+/// Checks that a line of `win_length` squares can fit on a board of the given size.
+pub(crate) fn check_win_length(size: usize, win_length: usize) -> Result<(), Error> {
+    if win_length == 0 || win_length > size {
+        return Err(anyhow!(
+            "the win length has to be between 1 and the size of the board ({size}), but it was {win_length}."
+        ));
+    }
+
+    Ok(())
+}
+
 impl TicTacToe {
-    fn new(dim: usize, size: usize) -> Self {
+    /// # Panics
+    ///
+    /// If `win_length` is zero or larger than `size`.
+    fn new(dim: usize, size: usize, win_length: usize) -> Self {
+        check_win_length(size, win_length).unwrap();
+
         // we want [SIZE; dim] but dim isn't a const - we have to get the slice from a vec
         let board = ArrayD::from_elem(IxDyn(&vec![size; dim]), None);
 
         Self {
             dim,
             size,
+            win_length,
             board,
             move_count: 0,
         }
     }
+}
 
-    /// Returns the square on this winning line.
-    fn winning_line(&self, point: &Dim<IxDynImpl>, offset: &[i32]) -> Option<Square> {
-        let square = self.board.get(point).unwrap();
-
-        if square.is_none() {
-            return None;
-        }
-
-        let mut n = 1;
+/// Returns the square on the line through `point` in the direction of `offset`,
+/// if there are at least `win_length` of that square in a row.
+pub(crate) fn winning_line(
+    board: &ArrayD<Option<Square>>,
+    point: &Dim<IxDynImpl>,
+    offset: &[i32],
+    win_length: usize,
+) -> Option<Square> {
+    let square = board.get(point).unwrap();
+
+    if square.is_none() {
+        return None;
+    }
 
-        let mut current = point.clone();
-        while let Some(new_current) = add_checked(current.clone(), offset.to_owned()) {
-            current = new_current;
-            if self.board.get(current.clone()) == Some(square) {
-                n += 1;
-            } else {
-                break;
-            }
-        }
-        let mut current = point.clone();
+    let mut n = 1;
 
-        while let Some(new_current) =
-            add_checked(current.clone(), offset.iter().map(|x| -x).collect())
-        {
-            current = new_current;
-            if self.board.get(current.clone()) == Some(square) {
-                n += 1;
-            } else {
-                break;
-            }
+    let mut current = point.clone();
+    while let Some(new_current) = add_checked(current.clone(), offset.to_owned()) {
+        current = new_current;
+        if board.get(current.clone()) == Some(square) {
+            n += 1;
+        } else {
+            break;
         }
+    }
+    let mut current = point.clone();
 
-        if n >= self.size {
-            *square
+    while let Some(new_current) = add_checked(current.clone(), offset.iter().map(|x| -x).collect())
+    {
+        current = new_current;
+        if board.get(current.clone()) == Some(square) {
+            n += 1;
         } else {
-            None
+            break;
         }
     }
+
+    if n >= win_length {
+        *square
+    } else {
+        None
+    }
+}
+
+/// Whether the `win_length` squares starting at `point` in the direction of `offset`
+/// fit on the board, and don't hold both an X and an O - so someone could still win with them.
+fn open_line(
+    board: &ArrayD<Option<Square>>,
+    point: &Dim<IxDynImpl>,
+    offset: &[i32],
+    win_length: usize,
+) -> bool {
+    let mut squares = vec![*board.get(point.clone()).unwrap()];
+
+    let mut current = point.clone();
+    for _ in 1..win_length {
+        let Some(next) = add_checked(current, offset.to_owned()) else {
+            return false;
+        };
+        let Some(square) = board.get(next.clone()) else {
+            return false;
+        };
+
+        squares.push(*square);
+        current = next;
+    }
+
+    !(squares.contains(&Some(Square::X)) && squares.contains(&Some(Square::O)))
+}
+
+impl TicTacToe {
+    /// Whether `m` is the square in the middle of the board, which only odd sizes have.
+    fn is_center(&self, m: &TicTacToeMove) -> bool {
+        self.size % 2 == 1 && m.0.as_array_view().iter().all(|&x| x == self.size / 2)
+    }
+
+    fn is_corner(&self, m: &TicTacToeMove) -> bool {
+        m.0.as_array_view()
+            .iter()
+            .all(|&x| x == 0 || x == self.size - 1)
+    }
 }
 
 impl Game for TicTacToe {
@@ -192,6 +263,7 @@ impl Game for TicTacToe {
         fn((Dim<IxDynImpl>, &Option<Square>)) -> Option<Self::Move>,
     >;
     type Player = PartizanPlayer;
+    type Key = Self;
     type MoveError = TicTacToeMoveError;
 
     fn max_moves(&self) -> Option<usize> {
@@ -215,8 +287,8 @@ impl Game for TicTacToe {
             }
 
             let point = index.into_dimension();
-            for offset in offsets(&point, self.size) {
-                if let Some(square) = self.winning_line(&point, &offset) {
+            for offset in offsets(&point, self.board.shape()) {
+                if let Some(square) = winning_line(&self.board, &point, &offset, self.win_length) {
                     return GameState::Win(square.to_player());
                 }
             }
@@ -249,32 +321,49 @@ impl Game for TicTacToe {
             })
     }
 
+    /// The center is part of the most lines, followed by the corners.
+    fn move_order_key(&self, m: &Self::Move) -> i32 {
+        if self.is_center(m) {
+            2
+        } else if self.is_corner(m) {
+            1
+        } else {
+            0
+        }
+    }
+
+    fn describe_move(&self, m: &Self::Move) -> String {
+        let square = Square::from_player(self.player());
+
+        if self.is_center(m) {
+            format!("{square:?} plays the center")
+        } else if self.is_corner(m) {
+            format!("{square:?} plays the corner at {m}")
+        } else {
+            format!("{square:?} plays {m}")
+        }
+    }
+
     fn find_immediately_resolvable_game(&self) -> Result<Option<Self>, Self::MoveError> {
-        // check if the amount of moves is less than (size * 2) - 1
+        // check if the amount of moves is less than (win_length * 2) - 1
         // if it is, then it's impossible to win
-        if self.move_count + 1 < self.size * 2 - 1 {
+        if self.move_count + 2 < self.win_length * 2 {
             return Ok(None);
         }
 
-        let mut best_non_winning_game: Option<Self> = None;
+        naive_immediately_resolvable_game(self)
+    }
 
-        for m in &mut self.possible_moves() {
-            let mut new_self = self.clone();
-            new_self.make_move(&m)?;
-            match new_self.state() {
-                GameState::Playable => continue,
-                GameState::Tie => best_non_winning_game = Some(new_self),
-                GameState::Win(winning_player) => {
-                    if winning_player == self.player().turn() {
-                        return Ok(Some(new_self));
-                    } else if best_non_winning_game.is_none() {
-                        best_non_winning_game = Some(new_self)
-                    }
-                }
-            };
-        }
+    /// No one can win once every line of `win_length` squares holds both an X and an O.
+    fn is_forced_draw(&self) -> bool {
+        let shape = self.board.shape();
 
-        Ok(best_non_winning_game)
+        !self.board.indexed_iter().any(|(index, _)| {
+            let point = index.into_dimension();
+            offsets(&point, shape)
+                .iter()
+                .any(|offset| open_line(&self.board, &point, offset, self.win_length))
+        })
     }
 
     fn player(&self) -> Self::Player {
@@ -284,9 +373,42 @@ impl Game for TicTacToe {
             PartizanPlayer::Right
         }
     }
+
+    fn canonical_key(&self) -> Option<Self> {
+        let mut smallest: Option<Vec<Option<Square>>> = None;
+
+        // the symmetries of a hypercube are every permutation of its axes,
+        // combined with reflecting any subset of those axes.
+        for permutation in (0..self.dim).permutations(self.dim) {
+            for reflections in 0..(1usize << self.dim) {
+                let mut view = self.board.view().permuted_axes(permutation.clone());
+                for axis in 0..self.dim {
+                    if reflections & (1 << axis) != 0 {
+                        view.invert_axis(Axis(axis));
+                    }
+                }
+
+                if smallest
+                    .as_ref()
+                    .map_or(true, |smallest| view.iter().lt(smallest.iter()))
+                {
+                    smallest = Some(view.iter().copied().collect());
+                }
+            }
+        }
+
+        Some(Self {
+            dim: self.dim,
+            size: self.size,
+            win_length: self.win_length,
+            board: ArrayD::from_shape_vec(self.board.raw_dim(), smallest?).unwrap(),
+            move_count: self.move_count,
+        })
+    }
 }
 
-fn offsets(dim: &Dim<IxDynImpl>, size: usize) -> Vec<Vec<i32>> {
+/// Returns every direction from `dim` that stays on a board of the given `shape`.
+pub(crate) fn offsets(dim: &Dim<IxDynImpl>, shape: &[usize]) -> Vec<Vec<i32>> {
     let values = (-1i32..=1).collect::<Vec<_>>(); // every offset
     let permutations = itertools::repeat_n(values.iter(), dim.ndim()).multi_cartesian_product();
 
@@ -303,7 +425,11 @@ fn offsets(dim: &Dim<IxDynImpl>, size: usize) -> Vec<Vec<i32>> {
             // filter out the permutations that are out of bounds [0, size)
             let result = add_checked(dim.clone(), permutation.to_owned());
             result.map_or(false, |result| {
-                result.as_array_view().iter().all(|x| *x < size)
+                result
+                    .as_array_view()
+                    .iter()
+                    .zip(shape)
+                    .all(|(x, size)| x < size)
             })
         })
         .collect()
@@ -311,7 +437,7 @@ fn offsets(dim: &Dim<IxDynImpl>, size: usize) -> Vec<Vec<i32>> {
 
 impl Display for TicTacToeMove {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self.0.as_array_view().as_slice().unwrap())
+        write!(f, "{}", self.0.as_array_view().iter().join("-"))
     }
 }
 
@@ -333,11 +459,19 @@ impl Debug for TicTacToe {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use game_solver::{move_scores, GameSolveError};
-    use std::collections::HashMap;
+    use game_solver::{
+        analyze,
+        game::{score_to_outcome, GameScoreOutcome},
+        is_move_winning, move_scores, pre_solve_check, principal_variation, solve, solve_best_move,
+        solve_best_move_ranked,
+        stats::Stats,
+        GameSolveError, MoveRanking, SolveMode,
+    };
+    use std::{collections::HashMap, sync::atomic::Ordering};
 
     fn move_scores_unwrapped(game: &TicTacToe) -> Vec<(TicTacToeMove, isize)> {
         move_scores(game, &mut HashMap::new(), None)
+            .unwrap()
             .collect::<Result<Vec<_>, GameSolveError<TicTacToe>>>()
             .unwrap()
     }
@@ -351,7 +485,7 @@ mod tests {
 
     #[test]
     fn test_middle_move() {
-        let mut game = TicTacToe::new(2, 3);
+        let mut game = TicTacToe::new(2, 3, 3);
         game.make_move(&TicTacToeMove(vec![0, 0].into_dimension()))
             .unwrap();
 
@@ -360,9 +494,147 @@ mod tests {
         assert_eq!(best_move, vec![1, 1].into_dimension());
     }
 
+    #[test]
+    fn test_solve_best_move() {
+        let mut game = TicTacToe::new(2, 3, 3);
+        game.make_move(&TicTacToeMove(vec![0, 0].into_dimension()))
+            .unwrap();
+
+        let (best_move, score) = solve_best_move(&game, &mut HashMap::new(), None)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(best_move.0, vec![1, 1].into_dimension());
+        assert_eq!(score, 0);
+    }
+
+    #[test]
+    fn test_move_ranking() {
+        let square = |point: [usize; 2]| TicTacToeMove(point.to_vec().into_dimension());
+
+        // every first move ties, so the center is only picked when ranking by move order
+        let game = TicTacToe::new(2, 3, 3);
+        let best = |ranking| {
+            solve_best_move_ranked(&game, &mut HashMap::new(), None, ranking)
+                .unwrap()
+                .unwrap()
+        };
+        assert_eq!(best(MoveRanking::PossibleMoves), (square([0, 0]), 0));
+        assert_eq!(best(MoveRanking::MoveOrderKey), (square([1, 1]), 0));
+
+        // and the analysis ranks its moves the same way
+        for ranking in [MoveRanking::PossibleMoves, MoveRanking::MoveOrderKey] {
+            let analysis = analyze(&game, &mut HashMap::new(), None, &None, ranking).unwrap();
+            assert_eq!((analysis[0].0.clone(), analysis[0].1), best(ranking));
+        }
+
+        // XX.
+        // .O.
+        // ..O
+        let mut game = TicTacToe::new(2, 3, 3);
+        for point in [[0, 0], [1, 1], [0, 1], [2, 2]] {
+            game.make_move(&square(point)).unwrap();
+        }
+
+        // X also wins by threatening two lines, but completing the line wins the soonest
+        let mut scores = move_scores_unwrapped(&game);
+        MoveRanking::MoveOrderKey.sort(&game, &mut scores);
+        assert_eq!(scores[0].0, square([0, 2]));
+        assert!(matches!(
+            score_to_outcome(&game, scores[1].1),
+            GameScoreOutcome::Win { .. }
+        ));
+        assert!(score_to_outcome(&game, scores[0].1) > score_to_outcome(&game, scores[1].1));
+
+        for ranking in [MoveRanking::PossibleMoves, MoveRanking::MoveOrderKey] {
+            let (best_move, _) = solve_best_move_ranked(&game, &mut HashMap::new(), None, ranking)
+                .unwrap()
+                .unwrap();
+            assert_eq!(best_move, square([0, 2]));
+        }
+    }
+
+    #[test]
+    fn test_principal_variation() {
+        let mut game = TicTacToe::new(2, 3, 3);
+        let mut transposition_table = HashMap::new();
+        solve(&game, &mut transposition_table, SolveMode::Strong, None).unwrap();
+
+        let line = principal_variation(&game, &mut transposition_table);
+
+        // perfect play always ends in a tie on a filled board
+        assert_eq!(line.len(), 9);
+        for m in &line {
+            game.make_move(m).unwrap();
+        }
+        assert_eq!(game.state(), GameState::Tie);
+    }
+
+    #[test]
+    fn test_canonical_key() {
+        let mut top_left = TicTacToe::new(2, 3, 3);
+        top_left
+            .make_move(&TicTacToeMove(vec![0, 0].into_dimension()))
+            .unwrap();
+
+        let mut bottom_right = TicTacToe::new(2, 3, 3);
+        bottom_right
+            .make_move(&TicTacToeMove(vec![2, 2].into_dimension()))
+            .unwrap();
+
+        let mut center = TicTacToe::new(2, 3, 3);
+        center
+            .make_move(&TicTacToeMove(vec![1, 1].into_dimension()))
+            .unwrap();
+
+        assert_eq!(top_left.canonical_key(), bottom_right.canonical_key());
+        assert_ne!(top_left.canonical_key(), center.canonical_key());
+    }
+
+    #[test]
+    fn test_symmetric_openings_share_entries() {
+        let mut top_left = TicTacToe::new(2, 3, 3);
+        top_left
+            .make_move(&TicTacToeMove(vec![0, 0].into_dimension()))
+            .unwrap();
+
+        let mut top_right = TicTacToe::new(2, 3, 3);
+        top_right
+            .make_move(&TicTacToeMove(vec![0, 2].into_dimension()))
+            .unwrap();
+
+        let mut transposition_table = HashMap::new();
+        let first = Stats::new(top_left.player(), top_left.move_count());
+        let score = solve(
+            &top_left,
+            &mut transposition_table,
+            SolveMode::Strong,
+            Some(&first),
+        )
+        .unwrap();
+
+        let second = Stats::new(top_right.player(), top_right.move_count());
+        assert_eq!(
+            solve(
+                &top_right,
+                &mut transposition_table,
+                SolveMode::Strong,
+                Some(&second)
+            )
+            .unwrap(),
+            score
+        );
+
+        // the reflected opening is already solved
+        assert!(
+            second.states_explored.load(Ordering::Relaxed)
+                < first.states_explored.load(Ordering::Relaxed) / 10
+        );
+    }
+
     #[test]
     fn test_always_tie() {
-        let game = TicTacToe::new(2, 3);
+        let game = TicTacToe::new(2, 3, 3);
 
         assert!(move_scores_unwrapped(&game)
             .iter()
@@ -371,7 +643,7 @@ mod tests {
 
     #[test]
     fn test_win() {
-        let mut game = TicTacToe::new(2, 3);
+        let mut game = TicTacToe::new(2, 3, 3);
 
         game.make_move(&TicTacToeMove(vec![0, 2].into_dimension()))
             .unwrap(); // X
@@ -389,7 +661,7 @@ mod tests {
 
     #[test]
     fn test_no_win() {
-        let mut game = TicTacToe::new(2, 3);
+        let mut game = TicTacToe::new(2, 3, 3);
 
         game.make_move(&TicTacToeMove(vec![0, 2].into_dimension()))
             .unwrap(); // X
@@ -403,9 +675,66 @@ mod tests {
         assert!(game.state() == GameState::Playable);
     }
 
+    #[test]
+    fn test_is_move_winning() {
+        let mut game = TicTacToe::new(2, 3, 3);
+        let center = TicTacToeMove(vec![1, 1].into_dimension());
+
+        // nothing wins against perfect play
+        assert!(!is_move_winning(&game, &center, &mut HashMap::new(), &None).unwrap());
+
+        for m in [[0, 2], [0, 1], [1, 1], [0, 0]] {
+            game.make_move(&TicTacToeMove(m.to_vec().into_dimension()))
+                .unwrap();
+        }
+
+        // X completes the diagonal
+        let mut transposition_table = HashMap::new();
+        let diagonal = TicTacToeMove(vec![2, 0].into_dimension());
+        assert!(is_move_winning(&game, &diagonal, &mut transposition_table, &None).unwrap());
+
+        for (m, score) in move_scores_unwrapped(&game) {
+            assert_eq!(
+                is_move_winning(&game, &m, &mut transposition_table, &None).unwrap(),
+                score > 0
+            );
+        }
+
+        assert!(matches!(
+            is_move_winning(&game, &center, &mut transposition_table, &None),
+            Err(GameSolveError::MoveError(_))
+        ));
+    }
+
+    #[test]
+    fn test_tied_game_is_over() {
+        let mut game = TicTacToe::new(2, 3, 3);
+
+        for m in [
+            [0, 0],
+            [1, 1],
+            [2, 2],
+            [0, 2],
+            [2, 0],
+            [1, 0],
+            [1, 2],
+            [2, 1],
+            [0, 1],
+        ] {
+            game.make_move(&TicTacToeMove(m.to_vec().into_dimension()))
+                .unwrap();
+        }
+
+        assert_eq!(pre_solve_check(&game), Some(GameState::Tie));
+        assert!(matches!(
+            move_scores(&game, &mut HashMap::new(), None),
+            Err(GameSolveError::GameOver(GameState::Tie))
+        ));
+    }
+
     #[test]
     fn test_win_3d() {
-        let mut game = TicTacToe::new(3, 3);
+        let mut game = TicTacToe::new(3, 3, 3);
 
         game.make_move(&TicTacToeMove(vec![0, 0, 0].into_dimension()))
             .unwrap(); // X
@@ -427,10 +756,193 @@ mod tests {
 
     #[test]
     fn test_always_tie_1d() {
-        let game = TicTacToe::new(1, 3);
+        let game = TicTacToe::new(1, 3, 3);
 
         assert!(move_scores_unwrapped(&game)
             .iter()
             .all(|(_, score)| *score == 0));
     }
+
+    #[test]
+    fn test_win_length() {
+        let mut game = TicTacToe::new(2, 5, 4);
+
+        for m in [[1, 1], [0, 0], [2, 2], [0, 1], [3, 3], [0, 2]] {
+            game.make_move(&TicTacToeMove(m.to_vec().into_dimension()))
+                .unwrap();
+        }
+
+        // three in a row isn't enough
+        assert!(game.state() == GameState::Playable);
+
+        game.make_move(&TicTacToeMove(vec![4, 4].into_dimension()))
+            .unwrap(); // X
+
+        // but four is, even though it doesn't span the board
+        assert!(game.state() == GameState::Win(PartizanPlayer::Left));
+    }
+
+    #[test]
+    fn invalid_win_lengths() {
+        let args = |size, win_length| TicTacToeArgs {
+            dimensions: 2,
+            size,
+            win_length,
+            moves: vec![],
+        };
+
+        assert!(TicTacToe::try_from(args(3, Some(0))).is_err());
+        assert!(TicTacToe::try_from(args(3, Some(4))).is_err());
+        assert!(TicTacToe::try_from(args(0, None)).is_err());
+        assert!(TicTacToe::try_from(args(3, Some(1))).is_ok());
+        assert!(TicTacToe::try_from(args(3, None)).is_ok());
+    }
+
+    /// Tic Tac Toe without its move ordering or forced draw check, to compare against.
+    #[derive(Clone, Hash, Eq, PartialEq, Debug)]
+    struct Unordered(TicTacToe);
+
+    impl Game for Unordered {
+        type Move = TicTacToeMove;
+        type Iter<'a> = <TicTacToe as Game>::Iter<'a>;
+        type Player = PartizanPlayer;
+        type Key = Self;
+        type MoveError = TicTacToeMoveError;
+
+        fn max_moves(&self) -> Option<usize> {
+            self.0.max_moves()
+        }
+
+        fn move_count(&self) -> usize {
+            self.0.move_count()
+        }
+
+        fn make_move(&mut self, m: &Self::Move) -> Result<(), Self::MoveError> {
+            self.0.make_move(m)
+        }
+
+        fn possible_moves(&self) -> Self::Iter<'_> {
+            self.0.possible_moves()
+        }
+
+        fn find_immediately_resolvable_game(&self) -> Result<Option<Self>, Self::MoveError> {
+            Ok(self.0.find_immediately_resolvable_game()?.map(Unordered))
+        }
+
+        fn state(&self) -> GameState<Self::Player> {
+            self.0.state()
+        }
+
+        fn player(&self) -> Self::Player {
+            self.0.player()
+        }
+
+        fn canonical_key(&self) -> Option<Self> {
+            self.0.canonical_key().map(Unordered)
+        }
+    }
+
+    #[test]
+    fn test_move_ordering() {
+        let game = TicTacToe::new(2, 3, 3);
+
+        let ordered = Stats::new(game.player(), game.move_count());
+        let score = solve(
+            &game,
+            &mut HashMap::new(),
+            SolveMode::Strong,
+            Some(&ordered),
+        )
+        .unwrap();
+
+        let unordered = Stats::new(game.player(), game.move_count());
+        assert_eq!(
+            solve(
+                &Unordered(game.clone()),
+                &mut HashMap::new(),
+                SolveMode::Strong,
+                Some(&unordered)
+            )
+            .unwrap(),
+            score
+        );
+
+        assert!(
+            ordered.states_explored.load(Ordering::Relaxed)
+                < unordered.states_explored.load(Ordering::Relaxed)
+        );
+    }
+
+    #[test]
+    fn test_describe_move() {
+        let mut game = TicTacToe::new(2, 3, 3);
+        let square = |point: [usize; 2]| TicTacToeMove(point.to_vec().into_dimension());
+
+        assert_eq!(game.describe_move(&square([1, 1])), "X plays the center");
+        game.make_move(&square([1, 1])).unwrap();
+        assert_eq!(
+            game.describe_move(&square([0, 2])),
+            "O plays the corner at 0-2"
+        );
+        assert_eq!(game.describe_move(&square([0, 1])), "O plays 0-1");
+
+        // even boards don't have a center
+        let game = TicTacToe::new(2, 4, 3);
+        assert_eq!(game.describe_move(&square([2, 2])), "X plays 2-2");
+    }
+
+    #[test]
+    fn test_forced_draw() {
+        let mut game = TicTacToe::new(2, 4, 4);
+        assert!(!game.is_forced_draw());
+
+        // XO..
+        // ..OX
+        // OX..
+        // ..XO
+        for point in [
+            [0, 0],
+            [0, 1],
+            [1, 3],
+            [1, 2],
+            [2, 1],
+            [2, 0],
+            [3, 2],
+            [3, 3],
+        ] {
+            game.make_move(&TicTacToeMove(point.to_vec().into_dimension()))
+                .unwrap();
+        }
+        assert_eq!(game.state(), GameState::Playable);
+        assert!(game.is_forced_draw());
+
+        let pruned = Stats::new(game.player(), game.move_count());
+        assert_eq!(
+            solve(&game, &mut HashMap::new(), SolveMode::Strong, Some(&pruned)).unwrap(),
+            0
+        );
+
+        // without the check, every remaining move has to be played out
+        let searched = Stats::new(game.player(), game.move_count());
+        assert_eq!(
+            solve(
+                &Unordered(game.clone()),
+                &mut HashMap::new(),
+                SolveMode::Strong,
+                Some(&searched)
+            )
+            .unwrap(),
+            0
+        );
+
+        assert!(
+            pruned.states_explored.load(Ordering::Relaxed)
+                < searched.states_explored.load(Ordering::Relaxed)
+        );
+    }
+
+    #[test]
+    fn moves_round_trip() {
+        crate::util::assert_moves_round_trip(&TicTacToe::new(2, 3, 3), 2);
+    }
 }
@@ -0,0 +1,121 @@
+use array2d::{Array2D, Error};
+
+/// A two-dimensional grid of booleans, indexed by `x` then `y`.
+///
+/// This lets board logic be shared between [`Array2D<bool>`] and [`BitBoard`].
+pub trait BoolGrid {
+    fn get_cell(&self, x: usize, y: usize) -> Option<bool>;
+    fn set_cell(&mut self, x: usize, y: usize, value: bool) -> Result<(), Error>;
+}
+
+impl BoolGrid for Array2D<bool> {
+    fn get_cell(&self, x: usize, y: usize) -> Option<bool> {
+        self.get(x, y).copied()
+    }
+
+    fn set_cell(&mut self, x: usize, y: usize, value: bool) -> Result<(), Error> {
+        self.set(x, y, value)
+    }
+}
+
+/// A grid of at most 128 booleans, stored as the bits of a `u128`.
+///
+/// Unlike [`Array2D`], this doesn't allocate, so cloning it is practically free.
+#[derive(Clone, Copy, Hash, Eq, PartialEq, Debug)]
+pub struct BitBoard {
+    width: usize,
+    height: usize,
+    bits: u128,
+}
+
+impl BitBoard {
+    /// The most cells a [`BitBoard`] can hold.
+    pub const MAX_CELLS: usize = u128::BITS as usize;
+
+    /// Creates a `width` x `height` board with every cell set to `value`.
+    ///
+    /// # Panics
+    ///
+    /// If the board has more than [`BitBoard::MAX_CELLS`] cells.
+    pub fn filled_with(value: bool, width: usize, height: usize) -> Self {
+        let cells = width * height;
+        assert!(
+            cells <= Self::MAX_CELLS,
+            "a {width}x{height} board doesn't fit in {} bits",
+            Self::MAX_CELLS
+        );
+
+        let bits = if !value {
+            0
+        } else if cells == Self::MAX_CELLS {
+            u128::MAX
+        } else {
+            (1 << cells) - 1
+        };
+
+        Self {
+            width,
+            height,
+            bits,
+        }
+    }
+
+    fn index(&self, x: usize, y: usize) -> Option<usize> {
+        if x < self.width && y < self.height {
+            Some(y * self.width + x)
+        } else {
+            None
+        }
+    }
+}
+
+impl BoolGrid for BitBoard {
+    fn get_cell(&self, x: usize, y: usize) -> Option<bool> {
+        self.index(x, y).map(|index| self.bits & (1 << index) != 0)
+    }
+
+    fn set_cell(&mut self, x: usize, y: usize, value: bool) -> Result<(), Error> {
+        let index = self.index(x, y).ok_or(Error::IndicesOutOfBounds(x, y))?;
+
+        if value {
+            self.bits |= 1 << index;
+        } else {
+            self.bits &= !(1 << index);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_array2d() {
+        let mut bitboard = BitBoard::filled_with(true, 16, 8);
+        let mut array = Array2D::filled_with(true, 16, 8);
+
+        for (x, y) in [(0, 0), (15, 7), (3, 5), (15, 0)] {
+            bitboard.set_cell(x, y, false).unwrap();
+            array.set_cell(x, y, false).unwrap();
+        }
+        bitboard.set_cell(3, 5, true).unwrap();
+        array.set_cell(3, 5, true).unwrap();
+
+        for x in 0..16 {
+            for y in 0..8 {
+                assert_eq!(bitboard.get_cell(x, y), array.get_cell(x, y));
+            }
+        }
+
+        assert_eq!(bitboard.get_cell(16, 0), None);
+        assert!(bitboard.set_cell(0, 8, true).is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_many_cells() {
+        BitBoard::filled_with(false, 13, 10);
+    }
+}
@@ -1 +1,2 @@
 pub mod egui_display;
+pub mod move_cell;
@@ -0,0 +1,7 @@
+use game_solver::game::Game;
+
+/// A game whose moves are made on the cells of a two-dimensional board.
+pub trait MoveToCell: Game {
+    /// The (row, column) of the cell that `m` is made on, if it's made on one.
+    fn move_to_cell(&self, m: &Self::Move) -> Option<(usize, usize)>;
+}
@@ -1,12 +1,14 @@
 use std::fmt::{Debug, Display};
 
-use game_solver::game::{Game, GameState};
 use anyhow::{anyhow, Result};
+use game_solver::game::{Game, GameState};
 
+pub mod bitboard;
 #[cfg(feature = "egui")]
 pub mod gui;
 pub mod move_natural;
 pub mod move_score;
+pub mod transcript;
 
 pub fn move_failable<T>(game: &mut T, m: &T::Move) -> Result<()>
 where
@@ -22,8 +24,66 @@ where
                 "Can't continue game if player {player:?} already won."
             ))
         }
+        GameState::WinMany(players) => {
+            return Err(anyhow!(
+                "Can't continue game if players {players:?} already won."
+            ))
+        }
     };
 
     game.make_move(m)
         .map_err(|err| anyhow!("Failed to move: {}", err))
 }
+
+/// Asserts that every move reachable in `depth` moves from `game`
+/// is parsed back from its [`Display`] output by its [`FromStr`](std::str::FromStr) implementation.
+#[cfg(test)]
+pub fn assert_moves_round_trip<T: Game>(game: &T, depth: usize)
+where
+    T::Move: Display + std::str::FromStr + PartialEq + Debug,
+    <T::Move as std::str::FromStr>::Err: Debug,
+{
+    if depth == 0 {
+        return;
+    }
+
+    for m in game.possible_moves() {
+        let text = m.to_string();
+        assert_eq!(
+            text.parse::<T::Move>().unwrap(),
+            m,
+            "{text} didn't round trip"
+        );
+
+        let mut next = game.clone();
+        next.make_move(&m).unwrap();
+        assert_moves_round_trip(&next, depth - 1);
+    }
+}
+
+/// Asserts that the [`Game::find_immediately_resolvable_game`] of every game reachable in `depth` moves
+/// from `game` ends the same way as [`naive_immediately_resolvable_game`](game_solver::game::naive_immediately_resolvable_game).
+#[cfg(test)]
+pub fn assert_resolvable_games_match<T: Game>(game: &T, depth: usize)
+where
+    T::MoveError: Debug,
+    T::Player: Debug,
+{
+    if game.state() != GameState::Playable {
+        return;
+    }
+
+    let state = |game: Option<T>| game.map(|game| game.state());
+    assert_eq!(
+        state(game.find_immediately_resolvable_game().unwrap()),
+        state(game_solver::game::naive_immediately_resolvable_game(game).unwrap())
+    );
+
+    if depth == 0 {
+        return;
+    }
+
+    for next in game.successors() {
+        assert_resolvable_games_match(&next.unwrap(), depth - 1);
+    }
+}
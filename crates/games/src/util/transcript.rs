@@ -0,0 +1,85 @@
+//! Transcripts of recorded games, which are replayed move by move.
+
+use std::{
+    fmt::{Debug, Display},
+    fs,
+    io::{self, Read},
+    path::Path,
+    str::FromStr,
+};
+
+use anyhow::{anyhow, Context, Result};
+use game_solver::game::Game;
+
+use super::move_failable;
+
+/// Reads a transcript from the file at `path`, or from stdin if `path` is `-`.
+pub fn read_transcript(path: &Path) -> Result<String> {
+    if path == Path::new("-") {
+        let mut transcript = String::new();
+        io::stdin()
+            .read_to_string(&mut transcript)
+            .context("Failed to read moves from stdin")?;
+        Ok(transcript)
+    } else {
+        fs::read_to_string(path)
+            .with_context(|| format!("Failed to read moves from {}", path.display()))
+    }
+}
+
+/// Makes every move of a transcript on `game`, in the same format as the moves given as arguments.
+/// Moves are separated by whitespace, so they can be given one per line.
+///
+/// The first move that can't be parsed or made stops the replay, and is reported by its (1-based) index.
+pub fn apply_transcript<T>(game: &mut T, transcript: &str) -> Result<()>
+where
+    T: Game,
+    T::Move: FromStr,
+    <T::Move as FromStr>::Err: Display,
+    T::MoveError: Display,
+    T::Player: Debug,
+{
+    for (index, text) in transcript.split_whitespace().enumerate() {
+        let index = index + 1;
+
+        let m = text
+            .parse::<T::Move>()
+            .map_err(|err| anyhow!("Move #{index} ({text}) couldn't be parsed: {err}"))?;
+
+        move_failable(game, &m)
+            .map_err(|err| anyhow!("Move #{index} ({text}) is illegal: {err}"))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{naive_nim::Nim, util::move_natural::NaturalMove};
+
+    use super::*;
+
+    #[test]
+    fn replays_moves() {
+        let mut game = Nim::new(vec![3, 5]);
+        apply_transcript(&mut game, "0-1\n1-2\n  0-2 ").unwrap();
+
+        let mut expected = Nim::new(vec![3, 5]);
+        for m in [[0, 1], [1, 2], [0, 2]] {
+            expected.make_move(&NaturalMove(m)).unwrap();
+        }
+        assert_eq!(game, expected);
+    }
+
+    #[test]
+    fn reports_the_first_bad_move() {
+        let mut game = Nim::new(vec![3, 5]);
+        let err = apply_transcript(&mut game, "0-1 1-9 0-1").unwrap_err();
+        assert!(err.to_string().starts_with("Move #2 (1-9) is illegal"));
+
+        let err = apply_transcript(&mut Nim::new(vec![3, 5]), "0-1 one").unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("Move #2 (one) couldn't be parsed"));
+    }
+}
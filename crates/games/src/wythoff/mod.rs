@@ -0,0 +1,398 @@
+#![doc = include_str!("./README.md")]
+
+use anyhow::{anyhow, Error};
+use clap::Args;
+use game_solver::{
+    game::{Game, GameState, Normal, NormalImpartial},
+    player::ImpartialPlayer,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{self, Debug, Display, Formatter},
+    hash::Hash,
+    str::FromStr,
+};
+use thiserror::Error;
+
+use crate::util::move_failable;
+
+/// Which heaps a move takes objects from.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum WythoffHeaps {
+    /// Only the heap with the given index, `0` or `1`
+    One(usize),
+    /// The same amount from both heaps
+    Both,
+}
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct WythoffMove {
+    pub heaps: WythoffHeaps,
+    pub amount: usize,
+}
+
+impl Display for WythoffMove {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.heaps {
+            WythoffHeaps::One(heap) => write!(f, "{heap}-{}", self.amount),
+            WythoffHeaps::Both => write!(f, "both-{}", self.amount),
+        }
+    }
+}
+
+impl FromStr for WythoffMove {
+    type Err = Error;
+
+    /// Parses moves written as `heap-amount`, where the heap is `0`, `1`, or `both`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (heaps, amount) = s
+            .split_once('-')
+            .ok_or_else(|| anyhow!("expected a move like 0-1 or both-1, but got {s}"))?;
+
+        let heaps = match heaps {
+            "both" => WythoffHeaps::Both,
+            heap => WythoffHeaps::One(heap.parse()?),
+        };
+
+        Ok(WythoffMove {
+            heaps,
+            amount: amount.parse()?,
+        })
+    }
+}
+
+#[derive(Error, Debug, Clone)]
+pub enum WythoffMoveError {
+    #[error("there is no heap {0}, only heaps 0 and 1.")]
+    HeapOutOfBounds(usize),
+    #[error("a move has to take at least one object.")]
+    EmptyRemoval,
+    #[error("can't take {0} when the heaps are {1:?}.")]
+    TooManyObjectsRemoval(WythoffMove, [usize; 2]),
+}
+
+#[derive(Clone, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Wythoff {
+    heaps: [usize; 2],
+    move_count: usize,
+    max_moves: Option<usize>,
+}
+
+/// `⌊√n⌋`, found exactly by Newton's method.
+fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+
+    let mut x = n;
+    let mut y = (x + 1) / 2;
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+
+    x
+}
+
+impl Wythoff {
+    /// Create a new game of Wythoff's game with two heaps of the given sizes.
+    pub fn new(first: usize, second: usize) -> Self {
+        Self {
+            heaps: [first, second],
+            move_count: 0,
+            // every move takes at least one object,
+            // and heaps too large to add up are too large to search anyway
+            max_moves: first.checked_add(second),
+        }
+    }
+
+    pub fn heaps(&self) -> [usize; 2] {
+        self.heaps
+    }
+
+    /// The `k`th cold position `(⌊kφ⌋, ⌊kφ⌋ + k)`, with the smaller heap first,
+    /// or `None` if its heaps are too large to fit in a `usize`.
+    ///
+    /// `⌊kφ⌋ = ⌊(k + k√5) / 2⌋`, and since `k√5` is irrational for every `k > 0`,
+    /// this is exactly `⌊(k + ⌊k√5⌋) / 2⌋` - so no floating point is needed.
+    pub fn cold_position(k: usize) -> Option<(usize, usize)> {
+        let k = k as u128;
+        // if 5k² doesn't fit in a u128, ⌊kφ⌋ + k doesn't fit in a usize either
+        let smaller = (k + isqrt(k.checked_mul(k)?.checked_mul(5)?)) / 2;
+
+        Some((
+            usize::try_from(smaller).ok()?,
+            usize::try_from(smaller + k).ok()?,
+        ))
+    }
+
+    /// Whether the player to move loses with perfect play, computed without search.
+    ///
+    /// Every cold position has a different difference between its heaps,
+    /// so this is the cold position whose difference matches.
+    pub fn is_cold(&self) -> bool {
+        let [first, second] = self.heaps;
+        let (smaller, larger) = (first.min(second), first.max(second));
+
+        Self::cold_position(larger - smaller) == Some((smaller, larger))
+    }
+}
+
+impl Normal for Wythoff {}
+impl NormalImpartial for Wythoff {}
+
+impl Game for Wythoff {
+    type Move = WythoffMove;
+    type Iter<'a> = std::vec::IntoIter<Self::Move>;
+    type Player = ImpartialPlayer;
+    type Key = Self;
+    type MoveError = WythoffMoveError;
+
+    fn max_moves(&self) -> Option<usize> {
+        self.max_moves
+    }
+
+    fn move_count(&self) -> usize {
+        self.move_count
+    }
+
+    fn make_move(&mut self, m: &Self::Move) -> Result<(), Self::MoveError> {
+        if m.amount == 0 {
+            return Err(WythoffMoveError::EmptyRemoval);
+        }
+
+        let heaps = match m.heaps {
+            WythoffHeaps::One(heap) if heap >= 2 => {
+                return Err(WythoffMoveError::HeapOutOfBounds(heap))
+            }
+            WythoffHeaps::One(heap) => heap..heap + 1,
+            WythoffHeaps::Both => 0..2,
+        };
+
+        if self.heaps[heaps.clone()]
+            .iter()
+            .any(|&heap| heap < m.amount)
+        {
+            return Err(WythoffMoveError::TooManyObjectsRemoval(*m, self.heaps));
+        }
+
+        for heap in &mut self.heaps[heaps] {
+            *heap -= m.amount;
+        }
+        self.move_count += 1;
+        Ok(())
+    }
+
+    const SUPPORTS_UNDO: bool = true;
+
+    fn undo_move(&mut self, m: &Self::Move) -> Result<(), Self::MoveError> {
+        let heaps = match m.heaps {
+            WythoffHeaps::One(heap) if heap >= 2 => {
+                return Err(WythoffMoveError::HeapOutOfBounds(heap))
+            }
+            WythoffHeaps::One(heap) => heap..heap + 1,
+            WythoffHeaps::Both => 0..2,
+        };
+
+        for heap in &mut self.heaps[heaps] {
+            *heap += m.amount;
+        }
+        self.move_count -= 1;
+        Ok(())
+    }
+
+    fn possible_moves(&self) -> Self::Iter<'_> {
+        let [first, second] = self.heaps;
+
+        (1..=first)
+            .map(|amount| (WythoffHeaps::One(0), amount))
+            .chain((1..=second).map(|amount| (WythoffHeaps::One(1), amount)))
+            .chain((1..=first.min(second)).map(|amount| (WythoffHeaps::Both, amount)))
+            .map(|(heaps, amount)| WythoffMove { heaps, amount })
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn state(&self) -> GameState<Self::Player> {
+        <Self as Normal>::state(self)
+    }
+
+    fn player(&self) -> Self::Player {
+        ImpartialPlayer::Next
+    }
+}
+
+impl Display for Wythoff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (i, heap) in self.heaps.iter().enumerate() {
+            writeln!(f, "Heap {i}: {heap}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Debug for Wythoff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        <Self as Display>::fmt(self, f)
+    }
+}
+
+/// Analyzes Wythoff's game.
+///
+#[doc = include_str!("./README.md")]
+#[derive(Args, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct WythoffArgs {
+    /// The sizes of the two heaps. For example, 5,8
+    configuration: String,
+    /// Wythoff moves, ordered as heap-amount: 0-2 both-1 ...
+    #[arg(value_parser = clap::value_parser!(WythoffMove))]
+    moves: Vec<WythoffMove>,
+}
+
+impl Default for WythoffArgs {
+    fn default() -> Self {
+        Self {
+            configuration: "5,8".to_string(),
+            moves: vec![],
+        }
+    }
+}
+
+impl TryFrom<WythoffArgs> for Wythoff {
+    type Error = Error;
+
+    fn try_from(args: WythoffArgs) -> Result<Self, Self::Error> {
+        let heaps = args
+            .configuration
+            .split(',')
+            .map(|heap| heap.parse::<usize>())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let [first, second] = heaps[..] else {
+            return Err(anyhow!(
+                "Wythoff's game has two heaps, but {} were given.",
+                heaps.len()
+            ));
+        };
+
+        let mut game = Wythoff::new(first, second);
+
+        // parse every move in args, e.g. 0-1 both-2 in args
+        for m in args.moves {
+            move_failable(&mut game, &m)?;
+        }
+
+        Ok(game)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use game_solver::{grundy_value, solve, SolveMode};
+    use nimnim::Nimber;
+
+    use super::*;
+
+    #[test]
+    fn cold_positions() {
+        let cold = (0..8).flat_map(Wythoff::cold_position).collect::<Vec<_>>();
+        assert_eq!(
+            cold,
+            vec![
+                (0, 0),
+                (1, 2),
+                (3, 5),
+                (4, 7),
+                (6, 10),
+                (8, 13),
+                (9, 15),
+                (11, 18)
+            ]
+        );
+
+        // cold positions of heaps far too large to search are still found
+        let k = 1 << 40;
+        let golden = (1.0 + 5f64.sqrt()) / 2.0;
+        let (smaller, larger) = Wythoff::cold_position(k).unwrap();
+        assert_eq!(larger - smaller, k);
+        assert!((smaller as f64 - k as f64 * golden).abs() < 1.0);
+        assert!(Wythoff::new(larger, smaller).is_cold());
+        assert!(!Wythoff::new(larger, smaller + 1).is_cold());
+
+        // and heaps too large for their cold position are never cold
+        assert_eq!(Wythoff::cold_position(usize::MAX), None);
+        assert_eq!(Wythoff::cold_position(usize::MAX / 2), None);
+        assert!(!Wythoff::new(0, usize::MAX).is_cold());
+        assert!(!Wythoff::new(usize::MAX / 2, usize::MAX).is_cold());
+        assert_eq!(Wythoff::new(usize::MAX, usize::MAX).max_moves(), None);
+    }
+
+    #[test]
+    fn search_matches_is_cold() {
+        let mut transposition_table = HashMap::new();
+
+        for first in 0..8 {
+            for second in 0..8 {
+                let game = Wythoff::new(first, second);
+                let next_wins =
+                    solve(&game, &mut HashMap::new(), SolveMode::Weak, None).unwrap() > 0;
+                let grundy = grundy_value(&game, &mut transposition_table).unwrap();
+
+                assert_eq!(game.is_cold(), !next_wins, "{first}, {second}");
+                assert_eq!(game.is_cold(), grundy == Nimber(0), "{first}, {second}");
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_moves() {
+        let mut game = Wythoff::new(2, 3);
+        let m = |heaps, amount| WythoffMove { heaps, amount };
+
+        assert!(game.make_move(&m(WythoffHeaps::One(2), 1)).is_err());
+        assert!(game.make_move(&m(WythoffHeaps::One(0), 0)).is_err());
+        assert!(game.make_move(&m(WythoffHeaps::Both, 3)).is_err());
+        assert_eq!(game.heaps(), [2, 3]);
+
+        game.make_move(&m(WythoffHeaps::Both, 2)).unwrap();
+        assert_eq!(game.heaps(), [0, 1]);
+    }
+
+    #[test]
+    fn args() {
+        let game = Wythoff::try_from(WythoffArgs {
+            configuration: "5,8".to_string(),
+            moves: vec!["both-2".parse().unwrap(), "0-1".parse().unwrap()],
+        })
+        .unwrap();
+        assert_eq!(game.heaps(), [2, 6]);
+
+        assert!(Wythoff::try_from(WythoffArgs {
+            configuration: "1,2,3".to_string(),
+            moves: vec![],
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn moves_round_trip() {
+        crate::util::assert_moves_round_trip(&Wythoff::new(3, 4), 2);
+        assert!("both".parse::<WythoffMove>().is_err());
+        assert!("all-2".parse::<WythoffMove>().is_err());
+    }
+
+    #[test]
+    fn undo_restores_the_game() {
+        let game = Wythoff::new(3, 4);
+
+        for m in game.possible_moves() {
+            let mut board = game.clone();
+            board.make_move(&m).unwrap();
+            board.undo_move(&m).unwrap();
+            assert_eq!(board, game);
+        }
+    }
+}
@@ -134,7 +134,7 @@ pub enum ZenerPosition {
     Gutter,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Direction {
     Up,
     Left,
@@ -171,8 +171,10 @@ impl Direction {
             return Ok(ZenerPosition::Gutter);
         }
 
-        if (HEIGHT as isize) < new_y {
-            return Err(anyhow::anyhow!("out of height bounds ({HEIGHT} < {new_y})"));
+        if (HEIGHT as isize) <= new_y || new_y < 0 {
+            return Err(anyhow::anyhow!(
+                "out of height bounds ({HEIGHT} <= {new_y})"
+            ));
         }
 
         let Ok(new_x) = new_x.try_into() else {
@@ -183,7 +185,7 @@ impl Direction {
             return Err(anyhow::anyhow!("out of width bounds ({WIDTH} <= {new_x})"));
         }
 
-        return Ok(ZenerPosition::Position(new_x.try_into().unwrap(), new_y.try_into().unwrap()));
+        Ok(ZenerPosition::Position(new_x, new_y.try_into().unwrap()))
     }
 }
 
@@ -203,6 +205,19 @@ impl FromStr for Direction {
     }
 }
 
+impl Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let direction = match self {
+            Direction::Up => "up",
+            Direction::Down => "down",
+            Direction::Left => "left",
+            Direction::Right => "right",
+        };
+
+        write!(f, "{direction}")
+    }
+}
+
 #[derive(Error, Clone, Debug)]
 pub enum ZenerMoveError {
     #[error("can not move from {0:?} since there's no piece!")]
@@ -221,7 +236,7 @@ pub enum ZenerMoveError {
     WrongPlayer(PartizanPlayer, PartizanPlayer),
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct ZenerMove {
     from: (usize, usize),
     to: Direction,
@@ -229,9 +244,7 @@ pub struct ZenerMove {
 
 impl Display for ZenerMove {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?} -> {:?}", self.from, self.to)?;
-
-        Ok(())
+        write!(f, "{}:{}:{}", self.from.0, self.from.1, self.to)
     }
 }
 
@@ -241,6 +254,7 @@ impl Game for Zener {
     type Iter<'a> = std::vec::IntoIter<Self::Move>;
     /// Left is bottom, Right is top
     type Player = PartizanPlayer;
+    type Key = Self;
     type MoveError = ZenerMoveError;
 
     fn max_moves(&self) -> Option<usize> {
@@ -313,7 +327,7 @@ impl Game for Zener {
         }
 
         self.move_count += 1;
-        self.loopy.mark_visited(prev);
+        self.loopy.mark_visited(&prev);
 
         Ok(())
     }
@@ -329,8 +343,7 @@ impl Game for Zener {
             let Some(cell) = self
                 .board
                 .get(x, y)
-                .map(|cell| cell.len().checked_sub(1).map(|i| cell.get(i)).flatten())
-                .flatten()
+                .and_then(|cell| cell.len().checked_sub(1).and_then(|i| cell.get(i)))
             else {
                 continue;
             };
@@ -357,7 +370,7 @@ impl Game for Zener {
             }
         }
 
-        return moves.into_iter();
+        moves.into_iter()
     }
 
     fn player(&self) -> Self::Player {
@@ -369,26 +382,20 @@ impl Game for Zener {
     }
 
     fn state(&self) -> GameState<Self::Player> {
-        if self.loopy.has_visited(self) {
+        if self.is_repetition_draw() {
             return GameState::Tie;
         }
 
-        <Self as Normal>::state(&self)
+        <Self as Normal>::state(self)
     }
 }
 
 /// Analyzes Zener.
 ///
 #[doc = include_str!("./README.md")]
-#[derive(Args, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[derive(Args, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Default)]
 pub struct ZenerArgs {}
 
-impl Default for ZenerArgs {
-    fn default() -> Self {
-        Self {}
-    }
-}
-
 impl FromStr for ZenerMove {
     type Err = anyhow::Error;
 
@@ -429,7 +436,7 @@ impl FromStr for ZenerMove {
 impl TryFrom<ZenerArgs> for Zener {
     type Error = anyhow::Error;
 
-    fn try_from(value: ZenerArgs) -> Result<Self, Self::Error> {
+    fn try_from(_value: ZenerArgs) -> Result<Self, Self::Error> {
         Ok(Zener::default())
     }
 }
@@ -459,3 +466,73 @@ impl Display for Zener {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use game_solver::game::{score_to_outcome, upper_bound, GameScoreOutcome};
+
+    use super::*;
+
+    #[test]
+    fn apply_to_position() {
+        assert!(matches!(
+            Direction::Down.apply_to_position((2, 3)),
+            Ok(ZenerPosition::Position(2, 4))
+        ));
+        assert!(matches!(
+            Direction::Up.apply_to_position((2, 3)),
+            Ok(ZenerPosition::Position(2, 2))
+        ));
+        assert!(matches!(
+            Direction::Up.apply_to_position((2, 0)),
+            Ok(ZenerPosition::Gutter)
+        ));
+        assert!(matches!(
+            Direction::Down.apply_to_position((2, HEIGHT - 1)),
+            Ok(ZenerPosition::Gutter)
+        ));
+        assert!(Direction::Left.apply_to_position((0, 3)).is_err());
+        assert!(Direction::Right.apply_to_position((WIDTH - 1, 3)).is_err());
+    }
+
+    #[test]
+    fn move_down() {
+        let mut game = Zener::default();
+
+        game.make_move(&ZenerMove::from_str("0:6:up").unwrap())
+            .unwrap();
+        game.make_move(&ZenerMove::from_str("0:0:down").unwrap())
+            .unwrap();
+
+        assert!(game.board[(0, 0)].is_empty());
+        assert_eq!(
+            game.board[(0, 1)].last(),
+            Some(&CellType(InnerCellType::Star, PartizanPlayer::Right))
+        );
+        assert_eq!(
+            game.board[(0, HEIGHT - 2)].last(),
+            Some(&CellType(InnerCellType::Circle, PartizanPlayer::Left))
+        );
+    }
+
+    #[test]
+    fn moves_round_trip() {
+        crate::util::assert_moves_round_trip(&Zener::default(), 2);
+    }
+
+    #[test]
+    fn outcomes_have_unknown_distances() {
+        let game = Zener::default();
+        let score = upper_bound(&game) - 1;
+
+        assert_eq!(
+            score_to_outcome(&game, score),
+            GameScoreOutcome::Win { distance: None }
+        );
+        assert_eq!(
+            score_to_outcome(&game, -score),
+            GameScoreOutcome::Loss { distance: None }
+        );
+        assert_eq!(score_to_outcome(&game, 0), GameScoreOutcome::Tie);
+    }
+}
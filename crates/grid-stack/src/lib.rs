@@ -6,6 +6,8 @@ use thiserror::Error;
 pub enum Error {
     #[error("Indices {0} and {1} are out of bounds")]
     IndicesOutOfBounds(usize, usize),
+    #[error("Expected {0} elements, but got {1}")]
+    DimensionMismatch(usize, usize),
 }
 
 #[derive(Clone, Hash, PartialEq, Eq, Debug)]
@@ -13,7 +15,9 @@ pub struct Grid<T, const W: usize, const H: usize, const SIZE: usize> {
     pub data: [T; SIZE],
 }
 
-impl<T: Clone + Copy + Default, const W: usize, const H: usize, const SIZE: usize> Default for Grid<T, W, H, SIZE> {
+impl<T: Clone + Copy + Default, const W: usize, const H: usize, const SIZE: usize> Default
+    for Grid<T, W, H, SIZE>
+{
     fn default() -> Self {
         assert!(SIZE == W * H, "SIZE must be equal to W * H");
         Self {
@@ -28,6 +32,49 @@ impl<T, const W: usize, const H: usize, const SIZE: usize> Grid<T, W, H, SIZE> {
         Self { data }
     }
 
+    /// Builds a grid from its elements in row-major order,
+    /// failing if there aren't exactly `W * H` of them.
+    pub fn from_row_major(data: &[T]) -> Result<Self, Error>
+    where
+        T: Clone,
+    {
+        assert!(SIZE == W * H, "SIZE must be equal to W * H");
+        let data = data
+            .to_vec()
+            .try_into()
+            .map_err(|data: Vec<T>| Error::DimensionMismatch(SIZE, data.len()))?;
+
+        Ok(Self { data })
+    }
+
+    /// Builds a grid from its rows, from top to bottom,
+    /// failing if there aren't exactly `H` rows of exactly `W` elements each.
+    pub fn from_rows<R: IntoIterator<Item = T>>(
+        rows: impl IntoIterator<Item = R>,
+    ) -> Result<Self, Error> {
+        assert!(SIZE == W * H, "SIZE must be equal to W * H");
+        let mut data = Vec::with_capacity(SIZE);
+        let mut height = 0;
+
+        for row in rows {
+            let start = data.len();
+            data.extend(row);
+            if data.len() - start != W {
+                return Err(Error::DimensionMismatch(W, data.len() - start));
+            }
+            height += 1;
+        }
+
+        if height != H {
+            return Err(Error::DimensionMismatch(H, height));
+        }
+
+        let data = data
+            .try_into()
+            .unwrap_or_else(|_| unreachable!("there are H rows of W elements"));
+        Ok(Self { data })
+    }
+
     pub fn filled_with(value: T) -> Self
     where
         T: Clone,
@@ -66,8 +113,12 @@ impl<T, const W: usize, const H: usize, const SIZE: usize> Grid<T, W, H, SIZE> {
 
     // iterators from array2d: https://github.com/HarrisonMc555/array2d
 
-    pub fn row_iter(&self, row_index: usize) -> Result<impl DoubleEndedIterator<Item = &T> + Clone, Error> {
-        let start = self.idx(0, row_index)
+    pub fn row_iter(
+        &self,
+        row_index: usize,
+    ) -> Result<impl DoubleEndedIterator<Item = &T> + Clone, Error> {
+        let start = self
+            .idx(0, row_index)
             .ok_or(Error::IndicesOutOfBounds(row_index, 0))?;
         let end = start + W;
         Ok(self.data[start..end].iter())
@@ -101,6 +152,159 @@ impl<T, const W: usize, const H: usize, const SIZE: usize> Grid<T, W, H, SIZE> {
         })
     }
 
+    /// Iterates over the `w` by `h` rectangle whose top left corner is `(x, y)`, in row-major order.
+    ///
+    /// Fails if the rectangle doesn't fit in the grid.
+    pub fn window(
+        &self,
+        x: usize,
+        y: usize,
+        w: usize,
+        h: usize,
+    ) -> Result<impl DoubleEndedIterator<Item = &T> + Clone, Error> {
+        if x.saturating_add(w) > W || y.saturating_add(h) > H {
+            return Err(Error::IndicesOutOfBounds(
+                x.saturating_add(w).saturating_sub(1),
+                y.saturating_add(h).saturating_sub(1),
+            ));
+        }
+
+        Ok(indices_row_major(w, h).map(move |(column, row)| &self[(x + column, y + row)]))
+    }
+
+    /// Iterates over the diagonal starting at `(start_x, start_y)`, going down and to the right.
+    pub fn diagonal_iter(
+        &self,
+        start_x: usize,
+        start_y: usize,
+    ) -> Result<impl DoubleEndedIterator<Item = &T> + Clone, Error> {
+        self.idx(start_x, start_y)
+            .ok_or(Error::IndicesOutOfBounds(start_x, start_y))?;
+        Ok(self.line_iter(start_x, start_y, false))
+    }
+
+    /// Iterates over the anti-diagonal starting at `(start_x, start_y)`, going down and to the left.
+    pub fn anti_diagonal_iter(
+        &self,
+        start_x: usize,
+        start_y: usize,
+    ) -> Result<impl DoubleEndedIterator<Item = &T> + Clone, Error> {
+        self.idx(start_x, start_y)
+            .ok_or(Error::IndicesOutOfBounds(start_x, start_y))?;
+        Ok(self.line_iter(start_x, start_y, true))
+    }
+
+    /// Iterates over every diagonal and anti-diagonal with at least `min_length` elements.
+    pub fn diagonals_iter(
+        &self,
+        min_length: usize,
+    ) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T> + Clone> {
+        // diagonals start on the top row, or on the left (or right, for anti-diagonals) column
+        let diagonals = (0..W)
+            .map(|x| (x, 0, false))
+            .chain((1..H).map(|y| (0, y, false)));
+        let anti_diagonals = (0..W)
+            .map(|x| (x, 0, true))
+            .chain((1..H).map(|y| (W.saturating_sub(1), y, true)));
+
+        diagonals
+            .chain(anti_diagonals)
+            .filter(move |&(x, y, anti)| Self::line_len(x, y, anti) >= min_length)
+            .map(move |(x, y, anti)| self.line_iter(x, y, anti))
+    }
+
+    fn line_len(x: usize, y: usize, anti: bool) -> usize {
+        if anti {
+            (x + 1).min(H - y)
+        } else {
+            (W - x).min(H - y)
+        }
+    }
+
+    fn line_iter(
+        &self,
+        x: usize,
+        y: usize,
+        anti: bool,
+    ) -> impl DoubleEndedIterator<Item = &T> + Clone {
+        (0..Self::line_len(x, y, anti))
+            .map(move |i| &self[(if anti { x - i } else { x + i }, y + i)])
+    }
+
+    /// Iterates over the orthogonal neighbors of `(x, y)` that are in bounds,
+    /// as `(x, y, value)`, starting above and going clockwise.
+    pub fn neighbors(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize, &T)> {
+        self.offset_iter(x, y, &[(0, -1), (1, 0), (0, 1), (-1, 0)])
+    }
+
+    /// Iterates over the orthogonal and diagonal neighbors of `(x, y)` that are in bounds,
+    /// as `(x, y, value)`, starting above and going clockwise.
+    pub fn neighbors_diagonal(
+        &self,
+        x: usize,
+        y: usize,
+    ) -> impl Iterator<Item = (usize, usize, &T)> {
+        self.offset_iter(
+            x,
+            y,
+            &[
+                (0, -1),
+                (1, -1),
+                (1, 0),
+                (1, 1),
+                (0, 1),
+                (-1, 1),
+                (-1, 0),
+                (-1, -1),
+            ],
+        )
+    }
+
+    fn offset_iter(
+        &self,
+        x: usize,
+        y: usize,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize, &T)> {
+        offsets.iter().filter_map(move |&(dx, dy)| {
+            let (x, y) = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+            self.idx(x, y).map(|i| (x, y, &self.data[i]))
+        })
+    }
+
+    /// Rotates the grid 90 degrees clockwise, which swaps its width and height.
+    pub fn rotate_90(&self) -> Grid<T, H, W, SIZE>
+    where
+        T: Clone,
+    {
+        Grid::new(core::array::from_fn(|i| {
+            let (x, y) = (i % H, i / H);
+            self[(y, H - 1 - x)].clone()
+        }))
+    }
+
+    /// Mirrors the grid from left to right.
+    pub fn flip_horizontal(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(core::array::from_fn(|i| {
+            let (x, y) = (i % W, i / W);
+            self[(W - 1 - x, y)].clone()
+        }))
+    }
+
+    /// Mirrors the grid from top to bottom.
+    pub fn flip_vertical(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(core::array::from_fn(|i| {
+            let (x, y) = (i % W, i / W);
+            self[(x, H - 1 - y)].clone()
+        }))
+    }
+
     pub fn indices_column_major(&self) -> impl DoubleEndedIterator<Item = (usize, usize)> + Clone {
         indices_column_major(W, H)
     }
@@ -110,6 +314,38 @@ impl<T, const W: usize, const H: usize, const SIZE: usize> Grid<T, W, H, SIZE> {
     }
 }
 
+/// Grids are serialized as a sequence of their elements, in row-major order.
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize, const W: usize, const H: usize, const SIZE: usize> serde::Serialize
+    for Grid<T, W, H, SIZE>
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.data.as_slice().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>, const W: usize, const H: usize, const SIZE: usize>
+    serde::Deserialize<'de> for Grid<T, W, H, SIZE>
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        if SIZE != W * H {
+            return Err(D::Error::custom(format!(
+                "SIZE ({SIZE}) must be equal to W * H ({W} * {H})"
+            )));
+        }
+
+        let data = Vec::<T>::deserialize(deserializer)?;
+        let length = data.len();
+        let data = data
+            .try_into()
+            .map_err(|_| D::Error::invalid_length(length, &format!("{SIZE} elements").as_str()))?;
+
+        Ok(Self { data })
+    }
+}
 
 fn indices_row_major(
     width: usize,
@@ -125,7 +361,9 @@ fn indices_column_major(
     (0..width).flat_map(move |column| (0..height).map(move |row| (column, row)))
 }
 
-impl<T, const W: usize, const H: usize, const SIZE: usize> Index<(usize, usize)> for Grid<T, W, H, SIZE> {
+impl<T, const W: usize, const H: usize, const SIZE: usize> Index<(usize, usize)>
+    for Grid<T, W, H, SIZE>
+{
     type Output = T;
 
     fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
@@ -133,8 +371,218 @@ impl<T, const W: usize, const H: usize, const SIZE: usize> Index<(usize, usize)>
     }
 }
 
-impl<T, const W: usize, const H: usize, const SIZE: usize> IndexMut<(usize, usize)> for Grid<T, W, H, SIZE> {
+impl<T, const W: usize, const H: usize, const SIZE: usize> IndexMut<(usize, usize)>
+    for Grid<T, W, H, SIZE>
+{
     fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
         &mut self.data[Self::direct_idx(x, y)]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// ```txt
+    /// 0 1 2
+    /// 3 4 5
+    /// 6 7 8
+    /// ```
+    fn labeled() -> Grid<usize, 3, 3, 9> {
+        Grid::new(core::array::from_fn(|i| i))
+    }
+
+    #[test]
+    fn diagonals() {
+        let grid = labeled();
+
+        assert_eq!(
+            grid.diagonal_iter(0, 0)
+                .unwrap()
+                .copied()
+                .collect::<Vec<_>>(),
+            vec![0, 4, 8]
+        );
+        assert_eq!(
+            grid.diagonal_iter(1, 0)
+                .unwrap()
+                .rev()
+                .copied()
+                .collect::<Vec<_>>(),
+            vec![5, 1]
+        );
+        assert_eq!(
+            grid.anti_diagonal_iter(2, 0)
+                .unwrap()
+                .copied()
+                .collect::<Vec<_>>(),
+            vec![2, 4, 6]
+        );
+        assert_eq!(
+            grid.anti_diagonal_iter(2, 1)
+                .unwrap()
+                .copied()
+                .collect::<Vec<_>>(),
+            vec![5, 7]
+        );
+        assert!(grid.diagonal_iter(3, 0).is_err());
+        assert!(grid.anti_diagonal_iter(0, 3).is_err());
+    }
+
+    #[test]
+    fn all_diagonals() {
+        let grid = labeled();
+        let diagonals = |min_length| {
+            grid.diagonals_iter(min_length)
+                .map(|diagonal| diagonal.copied().collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(diagonals(3), vec![vec![0, 4, 8], vec![2, 4, 6]]);
+        assert_eq!(
+            diagonals(2),
+            vec![
+                vec![0, 4, 8],
+                vec![1, 5],
+                vec![3, 7],
+                vec![1, 3],
+                vec![2, 4, 6],
+                vec![5, 7]
+            ]
+        );
+        assert_eq!(diagonals(1).len(), 10);
+    }
+
+    #[test]
+    fn rotate() {
+        // 0 1 2
+        // 3 4 5
+        let grid = Grid::<usize, 3, 2, 6>::new([0, 1, 2, 3, 4, 5]);
+
+        // 3 0
+        // 4 1
+        // 5 2
+        let rotated = grid.rotate_90();
+        assert_eq!(rotated, Grid::<usize, 2, 3, 6>::new([3, 0, 4, 1, 5, 2]));
+
+        assert_eq!(rotated.rotate_90().rotate_90().rotate_90(), grid);
+        assert_eq!(
+            labeled().rotate_90().rotate_90().rotate_90().rotate_90(),
+            labeled()
+        );
+    }
+
+    #[test]
+    fn flip() {
+        let grid = labeled();
+
+        assert_eq!(grid.flip_horizontal().data, [2, 1, 0, 5, 4, 3, 8, 7, 6]);
+        assert_eq!(grid.flip_vertical().data, [6, 7, 8, 3, 4, 5, 0, 1, 2]);
+        assert_eq!(grid.flip_horizontal().flip_horizontal(), grid);
+
+        // two flips are a half turn
+        assert_eq!(
+            grid.flip_horizontal().flip_vertical(),
+            grid.rotate_90().rotate_90()
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_roundtrip() {
+        let grid = labeled();
+        let bytes = bincode::serialize(&grid).unwrap();
+        assert_eq!(
+            bincode::deserialize::<Grid<usize, 3, 3, 9>>(&bytes).unwrap(),
+            grid
+        );
+
+        // the amount of elements has to match
+        assert!(bincode::deserialize::<Grid<usize, 2, 2, 4>>(&bytes).is_err());
+        // and so does SIZE
+        assert!(bincode::deserialize::<Grid<usize, 3, 2, 9>>(&bytes).is_err());
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = labeled();
+        let neighbors = |x, y| grid.neighbors(x, y).map(|(_, _, &v)| v).collect::<Vec<_>>();
+        let neighbors_diagonal = |x, y| {
+            grid.neighbors_diagonal(x, y)
+                .map(|(_, _, &v)| v)
+                .collect::<Vec<_>>()
+        };
+
+        // corner
+        assert_eq!(neighbors(0, 0), vec![1, 3]);
+        assert_eq!(neighbors_diagonal(2, 2), vec![5, 7, 4]);
+
+        // edge
+        assert_eq!(neighbors(1, 0), vec![2, 4, 0]);
+        assert_eq!(neighbors_diagonal(0, 1), vec![0, 1, 4, 7, 6]);
+
+        // center
+        assert_eq!(neighbors(1, 1), vec![1, 5, 7, 3]);
+        assert_eq!(neighbors_diagonal(1, 1), vec![1, 2, 5, 8, 7, 6, 3, 0]);
+
+        assert_eq!(
+            grid.neighbors(2, 1)
+                .map(|(x, y, _)| (x, y))
+                .collect::<Vec<_>>(),
+            vec![(2, 0), (2, 2), (1, 1)]
+        );
+    }
+
+    #[test]
+    fn from_rows() {
+        let rows = [[0, 1, 2], [3, 4, 5], [6, 7, 8]];
+        assert_eq!(Grid::from_rows(rows).unwrap(), labeled());
+        assert_eq!(
+            Grid::from_row_major(&[0, 1, 2, 3, 4, 5, 6, 7, 8]).unwrap(),
+            labeled()
+        );
+
+        // rows of the wrong width, or the wrong amount of rows or elements
+        assert!(matches!(
+            Grid::<usize, 3, 3, 9>::from_rows([vec![0, 1, 2], vec![3, 4], vec![5, 6, 7]]),
+            Err(Error::DimensionMismatch(3, 2))
+        ));
+        assert!(matches!(
+            Grid::<usize, 3, 3, 9>::from_rows([[0, 1, 2], [3, 4, 5]]),
+            Err(Error::DimensionMismatch(3, 2))
+        ));
+        assert!(matches!(
+            Grid::<usize, 3, 3, 9>::from_row_major(&[0; 10]),
+            Err(Error::DimensionMismatch(9, 10))
+        ));
+    }
+
+    #[test]
+    fn windows() {
+        let grid = labeled();
+        let window = |x, y, w, h| {
+            grid.window(x, y, w, h)
+                .map(|window| window.copied().collect::<Vec<_>>())
+        };
+
+        assert_eq!(window(0, 0, 2, 2).unwrap(), vec![0, 1, 3, 4]);
+        assert_eq!(window(0, 0, 3, 3).unwrap(), (0..9).collect::<Vec<_>>());
+
+        // windows touching the edges
+        assert_eq!(window(1, 1, 2, 2).unwrap(), vec![4, 5, 7, 8]);
+        assert_eq!(window(2, 0, 1, 3).unwrap(), vec![2, 5, 8]);
+        assert_eq!(window(0, 2, 3, 1).unwrap(), vec![6, 7, 8]);
+        assert!(window(3, 3, 0, 0).unwrap().is_empty());
+
+        // and going past them
+        assert!(matches!(
+            window(2, 2, 2, 1),
+            Err(Error::IndicesOutOfBounds(3, 2))
+        ));
+        assert!(matches!(
+            window(0, 1, 1, 3),
+            Err(Error::IndicesOutOfBounds(0, 3))
+        ));
+        assert!(window(usize::MAX, 0, 2, 1).is_err());
+    }
+}
@@ -1,4 +1,7 @@
-use std::{collections::HashSet, ops::Add};
+use std::{
+    collections::HashSet,
+    ops::{Add, Mul},
+};
 
 /// A nimber is the size of a heap in a single-stack nim game.
 ///
@@ -18,28 +21,109 @@ impl Add for Nimber {
     }
 }
 
+impl Mul for Nimber {
+    type Output = Nimber;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Nimber(nim_multiply(self.0, rhs.0, usize::BITS))
+    }
+}
+
+/// Nim-multiplies `a` and `b`, where both are less than `2^bits` and `bits` is a power of two.
+///
+/// This splits both numbers around the Fermat 2-power `F = 2^(bits / 2)`,
+/// using the fact that `F * F = F + F / 2` (in nim arithmetic) and that
+/// nimbers less than `F` multiply with `F` like ordinary numbers.
+fn nim_multiply(a: usize, b: usize, bits: u32) -> usize {
+    // 0 and 1 multiply like ordinary numbers
+    if a < 2 || b < 2 {
+        return a * b;
+    }
+
+    let half = bits / 2;
+    let mask = (1 << half) - 1;
+    let (a_high, a_low) = (a >> half, a & mask);
+    let (b_high, b_low) = (b >> half, b & mask);
+
+    let high = nim_multiply(a_high, b_high, half);
+    let low = nim_multiply(a_low, b_low, half);
+    // (a_high + a_low)(b_high + b_low) = high + low + (a_high * b_low + a_low * b_high)
+    let middle = nim_multiply(a_high ^ a_low, b_high ^ b_low, half) ^ low;
+
+    // high * F * F = high * F + high * (F / 2)
+    (middle << half) | (low ^ nim_multiply(high, 1 << (half - 1), half))
+}
+
 /// Returns Some(minimum excluded value of `list`), or `None` iff `list.is_empty()`
 pub fn mex(list: &[Nimber]) -> Option<Nimber> {
-    let mut mex: Option<Nimber> = None;
-    let mut set: HashSet<Nimber> = HashSet::with_capacity(list.len());
-
-    for item in list {
-        if set.insert(*item) {
-            if item > &mex.unwrap_or(Nimber(0)) {
-                mex = Some(*item)
-            }
-        }
+    if list.is_empty() {
+        return None;
     }
 
-    mex
+    let set: HashSet<Nimber> = list.iter().copied().collect();
+
+    // there are at most list.len() values in the set, so this always terminates.
+    (0..).map(Nimber).find(|nimber| !set.contains(nimber))
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::Nimber;
+    use crate::{mex, Nimber};
 
     #[test]
     fn add() {
         assert_eq!(Nimber(2) + Nimber(2), Nimber(0));
     }
+
+    #[test]
+    fn mul() {
+        assert_eq!(Nimber(2) * Nimber(3), Nimber(1));
+        assert_eq!(Nimber(2) * Nimber(2), Nimber(3));
+        assert_eq!(Nimber(4) * Nimber(4), Nimber(6));
+        assert_eq!(Nimber(8) * Nimber(8), Nimber(13));
+        assert_eq!(Nimber(16) * Nimber(16), Nimber(24));
+
+        // Fermat 2-powers multiply with smaller nimbers like ordinary numbers
+        assert_eq!(Nimber(16) * Nimber(5), Nimber(80));
+        assert_eq!(Nimber(1 << 32) * Nimber(7), Nimber(7 << 32));
+    }
+
+    #[test]
+    fn mul_is_a_field() {
+        for a in 0..32 {
+            assert_eq!(Nimber(a) * Nimber(1), Nimber(a));
+            assert_eq!(Nimber(a) * Nimber(0), Nimber(0));
+
+            for b in 0..32 {
+                assert_eq!(Nimber(a) * Nimber(b), Nimber(b) * Nimber(a));
+
+                for c in 0..8 {
+                    assert_eq!(
+                        Nimber(a) * (Nimber(b) + Nimber(c)),
+                        Nimber(a) * Nimber(b) + Nimber(a) * Nimber(c)
+                    );
+                    assert_eq!(
+                        (Nimber(a) * Nimber(b)) * Nimber(c),
+                        Nimber(a) * (Nimber(b) * Nimber(c))
+                    );
+                }
+            }
+
+            // the nimbers below 16 form a field, so every nonzero nimber has an inverse there
+            if (1..16).contains(&a) {
+                assert!((1..16).any(|b| Nimber(a) * Nimber(b) == Nimber(1)));
+            }
+        }
+    }
+
+    #[test]
+    fn mex_is_minimum_excluded() {
+        assert_eq!(mex(&[]), None);
+        assert_eq!(mex(&[Nimber(1), Nimber(2)]), Some(Nimber(0)));
+        assert_eq!(mex(&[Nimber(0), Nimber(2)]), Some(Nimber(1)));
+        assert_eq!(
+            mex(&[Nimber(2), Nimber(0), Nimber(1), Nimber(1)]),
+            Some(Nimber(3))
+        );
+    }
 }
@@ -4,11 +4,12 @@ use std::cmp::Ordering;
 use num_bigint::BigInt;
 
 /// The outcome of a game
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum Outcome {
     Left,
     Right,
     Next,
-    Previous
+    Previous,
 }
 
 /// General game utilities that should be
@@ -19,6 +20,15 @@ pub trait Game {
     /// `VecGame::zero().birthday() == 0`
     fn birthday(&self) -> BigInt;
 
+    /// The options Left can move to.
+    fn left_options(&self) -> Vec<Box<dyn Game>>;
+
+    /// The options Right can move to.
+    fn right_options(&self) -> Vec<Box<dyn Game>>;
+
+    /// Copies this game into a new box.
+    fn box_clone(&self) -> Box<dyn Game>;
+
     /// This operation is communative and associative.
     fn disjinctive_sum(&self, g: Box<dyn Game>) -> Box<dyn Game>;
 
@@ -30,36 +40,43 @@ pub trait Game {
 
     /// Gets the ordering of a game to another game
     /// Games define a partial ordering, and not a total ordering.
-    fn partial_cmp(&self, other: &Box<dyn Game>) -> Option<Ordering>;
+    fn partial_cmp(&self, other: &dyn Game) -> Option<Ordering>;
 
     /// Checks if two games are equal.
-    fn eq(&self, other: &Box<dyn Game>) -> bool {
+    fn eq(&self, other: &dyn Game) -> bool {
         self.partial_cmp(other) == Some(Ordering::Equal)
     }
 }
 
+impl Clone for Box<dyn Game> {
+    fn clone(&self) -> Self {
+        self.box_clone()
+    }
+}
+
 impl PartialEq for Box<dyn Game> {
     fn eq(&self, other: &Self) -> bool {
-        Game::eq(self.as_ref(), &Box::new(other))
+        Game::eq(self.as_ref(), other.as_ref())
     }
 }
 
 impl PartialOrd for Box<dyn Game> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Game::partial_cmp(self.as_ref(), &Box::new(other))
+        Game::partial_cmp(self.as_ref(), other.as_ref())
     }
 }
 
 /// We define a game by its left and right games,
 /// representing G = {left|right} and all of its options.
-/// 
+///
 /// This is not guaranteed to be the canonical game variant.
-/// 
+///
 /// This is stored as a vector of both the left and right game options: while this
 /// works for small games, this fails for big games. Other games that implement `Game` should be preferred.
+#[derive(Clone)]
 pub struct VecGame {
     left: Vec<Box<dyn Game>>,
-    right: Vec<Box<dyn Game>>
+    right: Vec<Box<dyn Game>>,
 }
 
 impl VecGame {
@@ -75,10 +92,7 @@ impl VecGame {
 
     /// Returns a game where the left and right options are flipped
     pub fn flip(self) -> Self {
-        VecGame::new(
-            self.right,
-            self.left
-        )
+        VecGame::new(self.right, self.left)
     }
 
     /// Returns the infinitesimal star game: {0|0} = *
@@ -97,38 +111,305 @@ impl VecGame {
     }
 
     pub const fn new(left: Vec<Box<dyn Game>>, right: Vec<Box<dyn Game>>) -> Self {
-        Self {
-            left,
-            right
+        Self { left, right }
+    }
+
+    /// Copies the options of any game into a `VecGame`.
+    pub fn from_game(game: &dyn Game) -> Self {
+        VecGame::new(game.left_options(), game.right_options())
+    }
+
+    /// Returns the canonical form of this game: the unique game with the fewest options
+    /// (and smallest birthday) that is equal to this game.
+    ///
+    /// This is done by canonicalizing every option, bypassing reversible options,
+    /// and removing dominated options.
+    pub fn canonicalize(&self) -> VecGame {
+        let canonicalize_options = |options: &[Box<dyn Game>]| {
+            options
+                .iter()
+                .map(|g| Box::new(VecGame::from_game(g.as_ref()).canonicalize()) as Box<dyn Game>)
+                .collect::<Vec<_>>()
+        };
+
+        let left = bypass_reversible(self, canonicalize_options(&self.left), true);
+        let right = bypass_reversible(self, canonicalize_options(&self.right), false);
+
+        VecGame::new(remove_dominated(left, true), remove_dominated(right, false))
+    }
+}
+
+/// Returns true if G ≥ H: that is, there is no right option of G
+/// that is ≤ H, and there is no left option of H that is ≥ G.
+fn greater_or_equal(g: &dyn Game, h: &dyn Game) -> bool {
+    !g.right_options()
+        .iter()
+        .any(|g_right| greater_or_equal(h, g_right.as_ref()))
+        && !h
+            .left_options()
+            .iter()
+            .any(|h_left| greater_or_equal(h_left.as_ref(), g))
+}
+
+/// Bypasses the reversible options of `game` for Left (if `is_left`) or Right.
+///
+/// A left option G^L is reversible if Right can respond with some G^LR ≤ G,
+/// in which case G^L can be replaced with the left options of G^LR (and vice versa for Right).
+fn bypass_reversible(
+    game: &dyn Game,
+    options: Vec<Box<dyn Game>>,
+    is_left: bool,
+) -> Vec<Box<dyn Game>> {
+    let mut pending = options;
+    let mut bypassed = Vec::new();
+
+    while let Some(option) = pending.pop() {
+        let responses = if is_left {
+            option.right_options()
+        } else {
+            option.left_options()
+        };
+
+        let reversing = responses.into_iter().find(|response| {
+            if is_left {
+                greater_or_equal(game, response.as_ref())
+            } else {
+                greater_or_equal(response.as_ref(), game)
+            }
+        });
+
+        match reversing {
+            // the replacement options may be reversible themselves
+            Some(response) if is_left => pending.extend(response.left_options()),
+            Some(response) => pending.extend(response.right_options()),
+            None => bypassed.push(option),
         }
     }
+
+    bypassed.reverse();
+    bypassed
+}
+
+/// Removes the options dominated by another option for Left (if `is_left`) or Right,
+/// keeping the first of any equal options.
+fn remove_dominated(options: Vec<Box<dyn Game>>, is_left: bool) -> Vec<Box<dyn Game>> {
+    // whether `a` is at least as good as `b` for the player
+    let at_least = |a: &dyn Game, b: &dyn Game| {
+        if is_left {
+            greater_or_equal(a, b)
+        } else {
+            greater_or_equal(b, a)
+        }
+    };
+
+    (0..options.len())
+        .filter(|&i| {
+            !(0..options.len()).any(|j| {
+                j != i
+                    && at_least(options[j].as_ref(), options[i].as_ref())
+                    && (j < i || !at_least(options[i].as_ref(), options[j].as_ref()))
+            })
+        })
+        .map(|i| options[i].clone())
+        .collect()
 }
 
 impl Game for VecGame {
     fn birthday(&self) -> BigInt {
-        if self.left.is_empty() && self.right.is_empty() {
-            return BigInt::ZERO
-        }
+        // a game is born the day after its latest option
+        self.left
+            .iter()
+            .chain(self.right.iter())
+            .map(|g| g.birthday() + 1)
+            .max()
+            .unwrap_or(BigInt::ZERO)
+    }
 
-        unimplemented!()
+    fn left_options(&self) -> Vec<Box<dyn Game>> {
+        self.left.clone()
     }
 
+    fn right_options(&self) -> Vec<Box<dyn Game>> {
+        self.right.clone()
+    }
+
+    fn box_clone(&self) -> Box<dyn Game> {
+        Box::new(self.clone())
+    }
+
+    /// G + H = { G^L + H, G + H^L | G^R + H, G + H^R }
     fn disjinctive_sum(&self, g: Box<dyn Game>) -> Box<dyn Game> {
-        unimplemented!()
+        let left = self
+            .left
+            .iter()
+            .map(|left| left.disjinctive_sum(g.clone()))
+            .chain(
+                g.left_options()
+                    .into_iter()
+                    .map(|left| self.disjinctive_sum(left)),
+            )
+            .collect();
+
+        let right = self
+            .right
+            .iter()
+            .map(|right| right.disjinctive_sum(g.clone()))
+            .chain(
+                g.right_options()
+                    .into_iter()
+                    .map(|right| self.disjinctive_sum(right)),
+            )
+            .collect();
+
+        Box::new(VecGame::new(left, right))
     }
 
     fn outcome(&self) -> Outcome {
-        unimplemented!()
+        // a player wins when moving first if they can move to a game
+        // that they win when moving second.
+        let left_wins_first = self
+            .left
+            .iter()
+            .any(|g| matches!(g.outcome(), Outcome::Left | Outcome::Previous));
+        let right_wins_first = self
+            .right
+            .iter()
+            .any(|g| matches!(g.outcome(), Outcome::Right | Outcome::Previous));
+
+        match (left_wins_first, right_wins_first) {
+            (true, true) => Outcome::Next,
+            (true, false) => Outcome::Left,
+            (false, true) => Outcome::Right,
+            (false, false) => Outcome::Previous,
+        }
     }
 
     fn negate(&self) -> Box<dyn Game> {
         Box::new(VecGame::new(
             self.left.iter().map(|g| g.negate()).collect(),
-            self.right.iter().map(|g| g.negate()).collect()
+            self.right.iter().map(|g| g.negate()).collect(),
         ))
     }
 
-    fn partial_cmp(&self, other: &Box<dyn Game>) -> Option<Ordering> {
-        unimplemented!()
+    fn partial_cmp(&self, other: &dyn Game) -> Option<Ordering> {
+        match (greater_or_equal(self, other), greater_or_equal(other, self)) {
+            (true, true) => Some(Ordering::Equal),
+            (true, false) => Some(Ordering::Greater),
+            (false, true) => Some(Ordering::Less),
+            (false, false) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outcome() {
+        assert_eq!(VecGame::zero().outcome(), Outcome::Previous);
+        assert_eq!(VecGame::star().outcome(), Outcome::Next);
+        assert_eq!(VecGame::up().outcome(), Outcome::Left);
+        assert_eq!(VecGame::down().outcome(), Outcome::Right);
+    }
+
+    #[test]
+    fn disjunctive_sum() {
+        // * + * = 0
+        let sum = VecGame::star().disjinctive_sum(Box::new(VecGame::star()));
+        assert_eq!(sum.outcome(), VecGame::zero().outcome());
+        assert_eq!(sum.birthday(), BigInt::from(2));
+
+        // 0 is the identity
+        let sum = VecGame::up().disjinctive_sum(Box::new(VecGame::zero()));
+        assert_eq!(sum.outcome(), Outcome::Left);
+        assert_eq!(sum.birthday(), BigInt::from(2));
+
+        // ↑ + * is a first player win
+        let sum = VecGame::up().disjinctive_sum(Box::new(VecGame::star()));
+        assert_eq!(sum.outcome(), Outcome::Next);
+
+        // ↑ + ↓ = 0
+        let sum = VecGame::up().disjinctive_sum(Box::new(VecGame::down()));
+        assert_eq!(sum.outcome(), Outcome::Previous);
+    }
+
+    /// {|0} = -1
+    fn negative_one() -> VecGame {
+        VecGame::new(vec![], vec![Box::new(VecGame::zero())])
+    }
+
+    #[test]
+    fn partial_cmp() {
+        let zero = VecGame::zero();
+        assert_eq!(zero.partial_cmp(&VecGame::zero()), Some(Ordering::Equal));
+        assert_eq!(VecGame::up().partial_cmp(&zero), Some(Ordering::Greater));
+        assert_eq!(VecGame::down().partial_cmp(&zero), Some(Ordering::Less));
+        assert_eq!(
+            negative_one().partial_cmp(&VecGame::down()),
+            Some(Ordering::Less)
+        );
+
+        // * is confused with 0, and ↑ is confused with *
+        assert_eq!(VecGame::star().partial_cmp(&zero), None);
+        assert_eq!(VecGame::up().partial_cmp(&VecGame::star()), None);
+
+        // ↑ + ↓ = 0
+        let sum = VecGame::up().disjinctive_sum(Box::new(VecGame::down()));
+        assert!(Game::eq(sum.as_ref(), &zero));
+    }
+
+    #[test]
+    fn canonicalize() {
+        // {0, -1|} = {0|} = 1, since 0 dominates -1
+        let game = VecGame::new(
+            vec![Box::new(VecGame::zero()), Box::new(negative_one())],
+            vec![],
+        );
+        let canonical = game.canonicalize();
+        assert_eq!(canonical.left.len(), 1);
+        assert!(canonical.right.is_empty());
+        assert!(Game::eq(&canonical, &game));
+
+        // {-1|1} = 0, since both moves are reversible through 0
+        let game = VecGame::new(
+            vec![Box::new(negative_one())],
+            vec![Box::new(negative_one().flip())],
+        );
+        let canonical = game.canonicalize();
+        assert!(canonical.left.is_empty() && canonical.right.is_empty());
+
+        // * + * = {*|*} = 0
+        let canonical = VecGame::from_game(
+            VecGame::star()
+                .disjinctive_sum(Box::new(VecGame::star()))
+                .as_ref(),
+        )
+        .canonicalize();
+        assert!(canonical.left.is_empty() && canonical.right.is_empty());
+
+        // {0, *|0} = ↑* is already canonical, since 0 and * are confused
+        let game = VecGame::new(
+            vec![Box::new(VecGame::zero()), Box::new(VecGame::star())],
+            vec![Box::new(VecGame::zero())],
+        );
+        let canonical = game.canonicalize();
+        assert_eq!(canonical.left.len(), 2);
+        assert_eq!(canonical.right.len(), 1);
+    }
+
+    #[test]
+    fn birthday() {
+        assert_eq!(VecGame::zero().birthday(), BigInt::from(0));
+        assert_eq!(VecGame::star().birthday(), BigInt::from(1));
+        assert_eq!(VecGame::up().birthday(), BigInt::from(2));
+        assert_eq!(VecGame::down().birthday(), BigInt::from(2));
+
+        // {0, ↑ | *}
+        let game = VecGame::new(
+            vec![Box::new(VecGame::zero()), Box::new(VecGame::up())],
+            vec![Box::new(VecGame::star())],
+        );
+        assert_eq!(game.birthday(), BigInt::from(3));
     }
 }